@@ -0,0 +1,245 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::header::{HeaderName, HeaderValue, USER_AGENT};
+use hyper::{HeaderMap, Uri};
+
+use super::cache::CacheBackend;
+use super::rate_limit::{RateLimit, RateLimiter};
+use super::record::{RecordStore, RecordingTransport, ReplayTransport};
+use super::retry::RetryPolicy;
+use super::transport::{HyperTransport, Transport};
+use super::ImdbSearchEngine;
+use crate::error::ImdbError;
+
+pub const DEFAULT_BASE_URI: &str = "https://www.imdb.com";
+
+/// Configures and builds an [`ImdbSearchEngine`].
+///
+/// Every option is optional, `ImdbSearchEngine::builder().build()` gives the
+/// same engine as `ImdbSearchEngine::new()`.
+#[derive(Clone, Default)]
+pub struct ImdbSearchEngineBuilder {
+    transport: Option<Arc<dyn Transport>>,
+    base_uri: Option<String>,
+    user_agent: Option<String>,
+    default_headers: Vec<(String, String)>,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    max_response_size: Option<usize>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<RateLimit>,
+    cache: Option<Arc<dyn CacheBackend>>,
+    record_to: Option<RecordStore>,
+    replay_from: Option<RecordStore>,
+}
+
+impl fmt::Debug for ImdbSearchEngineBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImdbSearchEngineBuilder")
+            .field("custom_transport", &self.transport.is_some())
+            .field("base_uri", &self.base_uri)
+            .field("user_agent", &self.user_agent)
+            .field("default_headers", &self.default_headers)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("request_timeout", &self.request_timeout)
+            .field("max_response_size", &self.max_response_size)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limit", &self.rate_limit)
+            .field("cache", &self.cache.is_some())
+            .field("record_to", &self.record_to)
+            .field("replay_from", &self.replay_from)
+            .finish()
+    }
+}
+
+impl ImdbSearchEngineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the default hyper transport.
+    ///
+    /// The user agent, headers and connect timeout only configure the default
+    /// transport and are ignored once this is set. The request timeout and max
+    /// response size still apply to every response.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Scheme and authority every request is sent to, e.g. `http://127.0.0.1:8080`.
+    pub fn base_uri(mut self, base_uri: impl Into<String>) -> Self {
+        self.base_uri = Some(base_uri.into());
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a header sent with every request. Repeated names are all sent.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Upper bound for a whole request, from sending it to reading the last body byte.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Responses with a larger body (in bytes) are rejected.
+    pub fn max_response_size(mut self, size: usize) -> Self {
+        self.max_response_size = Some(size);
+        self
+    }
+
+    /// Defaults to [`RetryPolicy::none`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Defaults to no limit at all.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Caches raw html by request uri, cached pages are still parsed on every hit.
+    pub fn cache(mut self, cache: impl CacheBackend + 'static) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Writes every fetched page, failed ones included, to `store`.
+    pub fn record_to(mut self, store: RecordStore) -> Self {
+        self.record_to = Some(store);
+        self
+    }
+
+    /// Serves pages recorded with [`Self::record_to`] instead of using the
+    /// network, takes precedence over [`Self::transport`].
+    pub fn replay_from(mut self, store: RecordStore) -> Self {
+        self.replay_from = Some(store);
+        self
+    }
+
+    pub fn build(self) -> Result<ImdbSearchEngine, ImdbError> {
+        let base_uri = self
+            .base_uri
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URI)
+            .trim_end_matches('/')
+            .to_string();
+        let parsed = Uri::from_str(&base_uri).map_err(invalid_config)?;
+        if parsed.scheme().is_none() || parsed.authority().is_none() {
+            return Err(ImdbError::InvalidConfig(format!(
+                "base uri `{}` needs a scheme and a host",
+                base_uri
+            )));
+        }
+
+        let mut default_headers = HeaderMap::new();
+        if let Some(user_agent) = self.user_agent {
+            default_headers.insert(
+                USER_AGENT,
+                HeaderValue::from_str(&user_agent).map_err(invalid_config)?,
+            );
+        }
+        for (name, value) in self.default_headers {
+            default_headers.append(
+                HeaderName::from_str(&name).map_err(invalid_config)?,
+                HeaderValue::from_str(&value).map_err(invalid_config)?,
+            );
+        }
+
+        let rate_limiter = match &self.rate_limit {
+            Some(rate_limit) => rate_limit.build()?,
+            None => RateLimiter::unlimited(),
+        };
+
+        let mut transport: Arc<dyn Transport> = match (self.replay_from, self.transport) {
+            (Some(store), _) => Arc::new(ReplayTransport::load(&store)?),
+            (None, Some(transport)) => transport,
+            (None, None) => Arc::new(HyperTransport::new(
+                self.connect_timeout,
+                default_headers.clone(),
+                self.max_response_size,
+            )),
+        };
+        if let Some(store) = self.record_to {
+            transport = Arc::new(RecordingTransport::new(transport, store));
+        }
+
+        Ok(ImdbSearchEngine {
+            transport,
+            base_uri,
+            default_headers,
+            request_timeout: self.request_timeout,
+            max_response_size: self.max_response_size,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limiter: Arc::new(rate_limiter),
+            cache: self.cache,
+        })
+    }
+}
+
+fn invalid_config(error: impl fmt::Display) -> ImdbError {
+    ImdbError::InvalidConfig(error.to_string())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use hyper::header::USER_AGENT;
+
+    use crate::search::{by::ByTitle, By};
+    use crate::ImdbSearchEngine;
+
+    #[test]
+    fn test_base_uri_is_used_by_get_uri() {
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://127.0.0.1:8080/")
+            .user_agent("swallower-tests")
+            .build()
+            .unwrap();
+
+        assert_eq!(engine.base_uri(), "http://127.0.0.1:8080");
+        assert_eq!(
+            engine.default_headers().get(USER_AGENT).unwrap(),
+            "swallower-tests"
+        );
+
+        let uri = ByTitle::default().get_uri(&engine, "star").unwrap();
+        assert_eq!(
+            uri.to_string(),
+            "http://127.0.0.1:8080/search/title/?title=star&start=1&count=10"
+        );
+    }
+
+    #[test]
+    fn test_invalid_options_are_rejected() {
+        assert!(ImdbSearchEngine::builder()
+            .base_uri("not a uri")
+            .build()
+            .is_err());
+        assert!(ImdbSearchEngine::builder()
+            .base_uri("/relative")
+            .build()
+            .is_err());
+        assert!(ImdbSearchEngine::builder()
+            .default_header("x-bad\n", "value")
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Stores raw html keyed by the final uri of a request.
+///
+/// Caching is best effort, a backend that fails to read or write should
+/// behave like a miss instead of failing the search.
+pub trait CacheBackend: Send + Sync {
+    fn get<'a>(&'a self, uri: &'a str) -> CacheFuture<'a, Option<String>>;
+    fn put<'a>(&'a self, uri: &'a str, html: String) -> CacheFuture<'a, ()>;
+}
+
+#[derive(Debug, Default)]
+struct LruEntries {
+    pages: HashMap<String, (Instant, String)>,
+    // Least recently used first.
+    order: VecDeque<String>,
+}
+
+impl LruEntries {
+    fn touch(&mut self, uri: &str) {
+        if let Some(index) = self.order.iter().position(|u| u == uri) {
+            let uri = self.order.remove(index).unwrap();
+            self.order.push_back(uri);
+        }
+    }
+
+    fn remove(&mut self, uri: &str) {
+        self.pages.remove(uri);
+        self.order.retain(|u| u != uri);
+    }
+}
+
+/// In memory cache keeping the `capacity` most recently used pages.
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<LruEntries>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: Mutex::new(LruEntries::default()),
+        }
+    }
+
+    fn get_sync(&self, uri: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let (stored_at, html) = entries.pages.get(uri)?;
+        if stored_at.elapsed() > self.ttl {
+            entries.remove(uri);
+            return None;
+        }
+
+        let html = html.clone();
+        entries.touch(uri);
+        Some(html)
+    }
+
+    fn put_sync(&self, uri: &str, html: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(uri);
+        while entries.order.len() >= self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.pages.remove(&oldest);
+            }
+        }
+        entries
+            .pages
+            .insert(uri.to_string(), (Instant::now(), html));
+        entries.order.push_back(uri.to_string());
+    }
+}
+
+impl CacheBackend for MemoryCache {
+    fn get<'a>(&'a self, uri: &'a str) -> CacheFuture<'a, Option<String>> {
+        let html = self.get_sync(uri);
+        Box::pin(async move { html })
+    }
+
+    fn put<'a>(&'a self, uri: &'a str, html: String) -> CacheFuture<'a, ()> {
+        self.put_sync(uri, html);
+        Box::pin(async {})
+    }
+}
+
+/// On disk cache writing one file per page into a directory.
+///
+/// Each file holds the uri on its first line and the raw html after it, the
+/// file modification time is used for the ttl. Pages are written to a
+/// temporary file first and renamed into place, so engines sharing the
+/// directory never read a half written page.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    directory: PathBuf,
+    ttl: Duration,
+}
+
+impl DiskCache {
+    pub fn new(directory: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            directory: directory.into(),
+            ttl,
+        }
+    }
+
+    fn path_for(&self, uri: &str) -> PathBuf {
+        self.directory.join(format!("{:016x}.html", fnv1a(uri)))
+    }
+
+    async fn read(&self, uri: &str) -> Option<String> {
+        let path = self.path_for(uri);
+        let modified = tokio::fs::metadata(&path).await.ok()?.modified().ok()?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or(Duration::ZERO);
+        if age > self.ttl {
+            let _ = tokio::fs::remove_file(&path).await;
+            return None;
+        }
+
+        let content = tokio::fs::read_to_string(&path).await.ok()?;
+        let (stored_uri, html) = content.split_once('\n')?;
+        // Different uris can hash to the same file.
+        if stored_uri != uri {
+            return None;
+        }
+
+        Some(html.to_string())
+    }
+
+    async fn write(&self, uri: &str, html: String) {
+        if tokio::fs::create_dir_all(&self.directory).await.is_err() {
+            return;
+        }
+        let path = self.path_for(uri);
+        // Unique per write, so concurrent writers of one page do not share it.
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let temp = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        if tokio::fs::write(&temp, format!("{}\n{}", uri, html))
+            .await
+            .is_err()
+            || tokio::fs::rename(&temp, &path).await.is_err()
+        {
+            let _ = tokio::fs::remove_file(&temp).await;
+        }
+    }
+}
+
+impl CacheBackend for DiskCache {
+    fn get<'a>(&'a self, uri: &'a str) -> CacheFuture<'a, Option<String>> {
+        Box::pin(self.read(uri))
+    }
+
+    fn put<'a>(&'a self, uri: &'a str, html: String) -> CacheFuture<'a, ()> {
+        Box::pin(self.write(uri, html))
+    }
+}
+
+/// Stable across runs and rust versions, unlike `DefaultHasher`.
+pub(crate) fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use super::{CacheBackend, DiskCache, MemoryCache};
+
+    #[tokio::test]
+    async fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2, Duration::from_secs(60));
+        cache.put("a", "page a".to_string()).await;
+        cache.put("b", "page b".to_string()).await;
+        assert_eq!(cache.get("a").await.as_deref(), Some("page a"));
+
+        cache.put("c", "page c".to_string()).await;
+        assert_eq!(cache.get("b").await, None);
+        assert_eq!(cache.get("a").await.as_deref(), Some("page a"));
+        assert_eq!(cache.get("c").await.as_deref(), Some("page c"));
+
+        let expired = MemoryCache::new(2, Duration::ZERO);
+        expired.put("a", "page a".to_string()).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(expired.get("a").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_round_trip() {
+        let directory =
+            std::env::temp_dir().join(format!("imdb-swallower-disk-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&directory, Duration::from_secs(60));
+        let uri = "https://www.imdb.com/find?s=tt&q=star";
+
+        assert_eq!(cache.get(uri).await, None);
+        cache.put(uri, "<html>\nstar\n</html>".to_string()).await;
+        assert_eq!(
+            cache.get(uri).await.as_deref(),
+            Some("<html>\nstar\n</html>")
+        );
+        // Only the renamed page is left behind.
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+
+        let _ = std::fs::remove_dir_all(directory);
+    }
+}
@@ -1,46 +1,670 @@
-use std::error::Error;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-use hyper::{client::HttpConnector, Client};
-use hyper_tls::HttpsConnector;
+use futures::stream::{self, Stream};
+use hyper::header::RETRY_AFTER;
+use hyper::{HeaderMap, StatusCode, Uri};
 use scraper::Html;
 use urlencoding::encode;
 
-use crate::search::By;
+use crate::error::ImdbError;
+use crate::search::by_release_info::ByReleaseInfo;
+use crate::search::by_season::{BySeason, Season};
+use crate::search::filters::country_code;
+use crate::search::{By, Paginated};
 
-#[derive(Debug, Clone)]
+pub mod builder;
+pub mod cache;
+pub mod rate_limit;
+pub mod record;
+pub mod retry;
+pub mod transport;
+
+pub use builder::ImdbSearchEngineBuilder;
+use cache::CacheBackend;
+use rate_limit::RateLimiter;
+use retry::RetryPolicy;
+use transport::{Response, Transport};
+
+#[derive(Clone)]
 pub struct ImdbSearchEngine {
-    hyper_client: Client<HttpsConnector<HttpConnector>>,
+    transport: Arc<dyn Transport>,
     base_uri: String,
+    default_headers: HeaderMap,
+    request_timeout: Option<Duration>,
+    max_response_size: Option<usize>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    cache: Option<Arc<dyn CacheBackend>>,
+}
+
+impl fmt::Debug for ImdbSearchEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImdbSearchEngine")
+            .field("base_uri", &self.base_uri)
+            .field("default_headers", &self.default_headers)
+            .field("request_timeout", &self.request_timeout)
+            .field("max_response_size", &self.max_response_size)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("cache", &self.cache.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ImdbSearchEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ImdbSearchEngine {
     pub fn new() -> Self {
-        let https = HttpsConnector::new();
-        let hyper_client = Client::builder().build::<_, hyper::Body>(https);
-        let base_uri = "https://www.imdb.com".to_string();
-        Self {
-            hyper_client,
-            base_uri,
-        }
+        Self::builder()
+            .build()
+            .expect("default engine configuration is valid")
+    }
+
+    pub fn builder() -> ImdbSearchEngineBuilder {
+        ImdbSearchEngineBuilder::new()
     }
 
     pub fn base_uri(&self) -> &str {
         self.base_uri.as_ref()
     }
 
-    pub async fn search_by<B: By>(
-        &self,
+    /// Headers the default transport sends with every request.
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// How long until the rate limit lets the next request through.
+    ///
+    /// Shared by all clones of this engine. Does not account for the max in
+    /// flight cap, which frees up whenever a running request finishes.
+    pub fn time_until_next_slot(&self) -> Duration {
+        self.rate_limiter.time_until_next_slot()
+    }
+
+    pub async fn search_by<B: By>(&self, by: B, query: &str) -> Result<B::ParseResult, ImdbError> {
+        self.search_by_ref(&by, query).await
+    }
+
+    /// Streams the items of every page, starting at `by`, until the results
+    /// run out or `limit` items were returned.
+    ///
+    /// Pages are fetched one at a time, as the stream is polled, through the
+    /// same retries, rate limit and cache as `search_by`. The stream ends after
+    /// the first error.
+    pub fn search_stream<'a, B>(
+        &'a self,
         by: B,
         query: &str,
-    ) -> Result<B::ParseResult, Box<dyn Error + Send + Sync>> {
+        limit: Option<usize>,
+    ) -> impl Stream<Item = Result<B::Item, ImdbError>> + 'a
+    where
+        B: Paginated + 'a,
+    {
+        let state = (Some(by), VecDeque::new(), limit, query.to_string());
+        stream::unfold(
+            state,
+            move |(mut next, mut buffer, mut remaining, query)| async move {
+                if remaining == Some(0) {
+                    return None;
+                }
+
+                loop {
+                    if let Some(item) = buffer.pop_front() {
+                        remaining = remaining.map(|r| r - 1);
+                        return Some((Ok(item), (next, buffer, remaining, query)));
+                    }
+
+                    let by = next.take()?;
+                    match self.search_by_ref(&by, &query).await {
+                        Ok(result) => {
+                            next = by.next_page(&result);
+                            buffer.extend(B::into_items(result));
+                        }
+                        Err(error) => return Some((Err(error), (None, buffer, remaining, query))),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Every season of a series, one request per season, in the order of the
+    /// season picker. The "Unknown" season of unnumbered episodes is included
+    /// when the series has one.
+    ///
+    /// The episodes page is first loaded without a season to learn which
+    /// seasons exist, the season it shows is not loaded twice.
+    pub async fn all_seasons(&self, series_id: &str) -> Result<Vec<Season>, ImdbError> {
+        let first = self.search_by_ref(&BySeason::default(), series_id).await?;
+        let numbers = first.seasons().to_vec();
+        if numbers.is_empty() {
+            return Ok(vec![first]);
+        }
+
+        let shown = first.season();
+        let mut first = Some(first);
+        let mut seasons = vec![];
+        for number in numbers {
+            if shown == Some(number) {
+                if let Some(first) = first.take() {
+                    seasons.push(first);
+                    continue;
+                }
+            }
+            seasons.push(self.search_by_ref(&BySeason::of(number), series_id).await?);
+        }
+
+        Ok(seasons)
+    }
+
+    /// The title of `title_id` as released in `country`, an ISO 3166 code
+    /// like `fr`, falling back to the original title.
+    ///
+    /// One request to the release info page, which lists the titles of every
+    /// country, unlike the title page read by
+    /// [`ByTitleId`](crate::search::by::ByTitleId).
+    pub async fn localised_title(
+        &self,
+        title_id: &str,
+        country: &str,
+    ) -> Result<Option<String>, ImdbError> {
+        let country = country_code(country)?;
+        let info = self
+            .search_by_ref(&ByReleaseInfo::default(), title_id)
+            .await?;
+
+        Ok(info
+            .localised_title(&country)
+            .or_else(|| info.original_title())
+            .map(|title| title.to_string()))
+    }
+
+    async fn search_by_ref<B: By>(&self, by: &B, query: &str) -> Result<B::ParseResult, ImdbError> {
         let encoded_query = encode(query).to_string();
-        let uri = by.get_uri(self, &encoded_query);
-        let mut resp = self.hyper_client.get(uri).await?;
-        Ok(by.parse_result(Html::parse_document(&String::from_utf8(
-            hyper::body::to_bytes(resp.body_mut())
-                .await?
-                .into_iter()
-                .collect(),
-        )?)))
+        let uri = by.get_uri(self, &encoded_query)?;
+        let html = self.fetch_html(uri).await?;
+
+        Ok(by.parse_result(Html::parse_document(&html))?)
+    }
+
+    /// Raw html for `uri`, from the cache when there is a fresh copy.
+    async fn fetch_html(&self, uri: Uri) -> Result<String, ImdbError> {
+        let cache_key = uri.to_string();
+        if let Some(cache) = &self.cache {
+            if let Some(html) = cache.get(&cache_key).await {
+                return Ok(html);
+            }
+        }
+
+        let resp = self.fetch_with_retries(uri).await?;
+        let html = String::from_utf8(resp.body().to_vec())?;
+
+        if let Some(cache) = &self.cache {
+            cache.put(&cache_key, html.clone()).await;
+        }
+
+        Ok(html)
+    }
+
+    async fn fetch_with_retries(&self, uri: Uri) -> Result<Response, ImdbError> {
+        let mut attempt = 1;
+        loop {
+            let error = match self.fetch(uri.clone()).await {
+                Ok(resp) => return Ok(resp),
+                Err(error) => error,
+            };
+
+            let delay = match self.retry_policy.next_delay(attempt, &error) {
+                Some(delay) => delay,
+                None => return Err(error),
+            };
+            self.retry_policy.notify(&uri, attempt, &error, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn fetch(&self, uri: Uri) -> Result<Response, ImdbError> {
+        let permit = self.rate_limiter.acquire().await;
+        // Enforced here rather than in the transport so custom and replay
+        // transports are held to them too.
+        let resp = match self.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.transport.fetch(uri))
+                .await
+                .map_err(ImdbError::transport)??,
+            None => self.transport.fetch(uri).await?,
+        };
+        drop(permit);
+
+        if let Some(max) = self.max_response_size {
+            if resp.body().len() > max {
+                return Err(ImdbError::transport(format!(
+                    "response body exceeds {} bytes",
+                    max
+                )));
+            }
+        }
+
+        let status = resp.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(ImdbError::RateLimited {
+                retry_after: parse_retry_after(resp.headers()),
+            });
+        }
+        if !status.is_success() {
+            return Err(ImdbError::HttpStatus {
+                status,
+                retry_after: parse_retry_after(resp.headers()),
+            });
+        }
+
+        Ok(resp)
+    }
+}
+
+/// `Retry-After` is either a number of seconds or an http date.
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use hyper::header::RETRY_AFTER;
+    use hyper::{HeaderMap, StatusCode, Uri};
+
+    use crate::error::ImdbError;
+    use crate::prelude::{
+        FixtureTransport, MemoryCache, RateLimit, Response, RetryPolicy, Transport, TransportFuture,
+    };
+    use crate::search::by::{ByTitle, ByTitleFind};
+    use crate::search::results::by_season::SeasonNumber;
+    use crate::ImdbSearchEngine;
+
+    const TITLE_SEARCH_PAGE: &str = r#"
+<div class="lister-list">
+    <div class="lister-item mode-advanced">
+        <div class="lister-item-image float-left">
+            <a href="/title/tt0076759/"><img src="https://m.media-amazon.com/images/M/star_wars.jpg"></a>
+        </div>
+        <div class="lister-item-content">
+            <h3 class="lister-item-header">
+                <span class="lister-item-index unbold text-primary">1.</span>
+                <a href="/title/tt0076759/">Star Wars</a>
+                <span class="lister-item-year text-muted unbold">(1977)</span>
+            </h3>
+            <p class="text-muted">
+                <span class="certificate">PG</span>
+                <span class="runtime">121 min</span>
+                <span class="genre">Action, Adventure, Fantasy</span>
+            </p>
+            <div class="ratings-bar">
+                <div class="inline-block ratings-imdb-rating" data-value="8.6"><strong>8.6</strong></div>
+            </div>
+            <p class="text-muted">Luke Skywalker joins forces with a Jedi Knight.</p>
+            <p class="">
+                Director:
+                <a href="/name/nm0000184/">George Lucas</a>
+                <span class="ghost">|</span>
+                Stars:
+                <a href="/name/nm0000434/">Mark Hamill</a>,
+                <a href="/name/nm0000148/">Harrison Ford</a>
+            </p>
+        </div>
+    </div>
+</div>
+"#;
+
+    #[tokio::test]
+    async fn test_search_by_with_fixture_transport() {
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://fixtures")
+            .transport(FixtureTransport::new().with_page(
+                "http://fixtures/search/title/?title=star%20wars&start=1&count=10",
+                TITLE_SEARCH_PAGE,
+            ))
+            .build()
+            .unwrap();
+
+        let result = engine
+            .search_by(ByTitle::default(), "star wars")
+            .await
+            .unwrap();
+
+        let item = result.items().first().unwrap();
+        assert_eq!(item.title().text(), "Star Wars");
+        assert_eq!(item.years(), "(1977)");
+        assert_eq!(item.rating(), "8.6");
+        assert_eq!(item.directors()[0].name(), "George Lucas");
+        assert_eq!(item.stars().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_success_status_is_an_error() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://fixtures")
+            .transport(
+                FixtureTransport::new()
+                    .with_response(
+                        "http://fixtures/find?s=tt&q=busy",
+                        Response::new(StatusCode::SERVICE_UNAVAILABLE, headers.clone(), ""),
+                    )
+                    .with_response(
+                        "http://fixtures/find?s=tt&q=slow%20down",
+                        Response::new(StatusCode::TOO_MANY_REQUESTS, headers, ""),
+                    ),
+            )
+            .build()
+            .unwrap();
+
+        match engine.search_by(ByTitleFind::default(), "busy").await {
+            Err(ImdbError::HttpStatus {
+                status,
+                retry_after,
+            }) => {
+                assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+                assert_eq!(retry_after, Some(Duration::from_secs(120)));
+            }
+            other => panic!("expected an http status error, got {:?}", other.err()),
+        }
+
+        assert!(matches!(
+            engine.search_by(ByTitleFind::default(), "slow down").await,
+            Err(ImdbError::RateLimited { .. })
+        ));
+        assert!(matches!(
+            engine.search_by(ByTitleFind::default(), "missing").await,
+            Err(ImdbError::HttpStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            })
+        ));
+    }
+
+    struct FlakyTransport {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    impl Transport for FlakyTransport {
+        fn fetch(&self, _uri: Uri) -> TransportFuture<'_> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let resp = if call < self.failures {
+                Response::new(StatusCode::SERVICE_UNAVAILABLE, HeaderMap::new(), "")
+            } else {
+                Response::new(StatusCode::OK, HeaderMap::new(), TITLE_SEARCH_PAGE)
+            };
+            Box::pin(async move { Ok(resp) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let attempts = Arc::new(Mutex::new(vec![]));
+        let seen = attempts.clone();
+        let engine = ImdbSearchEngine::builder()
+            .transport(FlakyTransport {
+                failures: 2,
+                calls: AtomicUsize::new(0),
+            })
+            .retry_policy(
+                RetryPolicy::default()
+                    .base_delay(Duration::from_millis(1))
+                    .on_retry(move |attempt| seen.lock().unwrap().push(attempt.attempt())),
+            )
+            .build()
+            .unwrap();
+
+        let result = engine.search_by(ByTitle::default(), "star wars").await;
+        assert_eq!(result.unwrap().items().len(), 1);
+        assert_eq!(*attempts.lock().unwrap(), vec![1, 2]);
+
+        let engine = ImdbSearchEngine::builder()
+            .transport(FlakyTransport {
+                failures: 5,
+                calls: AtomicUsize::new(0),
+            })
+            .retry_policy(RetryPolicy::default().base_delay(Duration::from_millis(1)))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            engine.search_by(ByTitle::default(), "star wars").await,
+            Err(ImdbError::HttpStatus { .. })
+        ));
+    }
+
+    /// Answers every uri with `TITLE_SEARCH_PAGE` after `delay`, counting
+    /// how many requests it had to serve at once.
+    #[derive(Default)]
+    struct SlowTransport {
+        delay: Duration,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl Transport for SlowTransport {
+        fn fetch(&self, _uri: Uri) -> TransportFuture<'_> {
+            Box::pin(async move {
+                let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(self.delay).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(Response::new(
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    TITLE_SEARCH_PAGE,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport_gets_timeout_and_size_limit() {
+        let engine = ImdbSearchEngine::builder()
+            .transport(SlowTransport {
+                delay: Duration::from_millis(200),
+                ..Default::default()
+            })
+            .request_timeout(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            engine.search_by(ByTitle::default(), "star wars").await,
+            Err(ImdbError::Transport(_))
+        ));
+
+        let engine = ImdbSearchEngine::builder()
+            .transport(SlowTransport::default())
+            .max_response_size(100)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            engine.search_by(ByTitle::default(), "star wars").await,
+            Err(ImdbError::Transport(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_the_rate_limit() {
+        let engine = ImdbSearchEngine::builder()
+            .transport(SlowTransport::default())
+            .rate_limit(RateLimit::new().per_minute(2))
+            .build()
+            .unwrap();
+        let clone = engine.clone();
+
+        engine
+            .search_by(ByTitle::default(), "star wars")
+            .await
+            .unwrap();
+        assert_eq!(clone.time_until_next_slot(), Duration::ZERO);
+        clone
+            .search_by(ByTitle::default(), "star wars")
+            .await
+            .unwrap();
+        assert!(engine.time_until_next_slot() > Duration::from_secs(20));
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_requests_are_capped() {
+        let transport = Arc::new(SlowTransport {
+            delay: Duration::from_millis(50),
+            ..Default::default()
+        });
+        let engine = ImdbSearchEngine::builder()
+            .transport(transport.clone())
+            .rate_limit(RateLimit::new().max_in_flight(2))
+            .build()
+            .unwrap();
+
+        let searches = (0..6).map(|_| {
+            let engine = engine.clone();
+            tokio::spawn(async move { engine.search_by(ByTitle::default(), "star wars").await })
+        });
+        for search in futures::future::join_all(searches).await {
+            search.unwrap().unwrap();
+        }
+        assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_hits_skip_the_transport() {
+        let transport = Arc::new(FlakyTransport {
+            failures: 0,
+            calls: AtomicUsize::new(0),
+        });
+        let engine = ImdbSearchEngine::builder()
+            .transport(transport.clone())
+            .cache(MemoryCache::new(10, Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            let result = engine.search_by(ByTitle::default(), "star wars").await;
+            assert_eq!(result.unwrap().items().len(), 1);
+        }
+        assert_eq!(transport.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_stream_follows_pages() {
+        let page = |position: u32| {
+            TITLE_SEARCH_PAGE.replace(
+                r#"<div class="lister-list">"#,
+                &format!(
+                    r#"<div class="desc"><span>{0}-{0} of 2 titles.</span></div><div class="lister-list">"#,
+                    position
+                ),
+            )
+        };
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://fixtures")
+            .transport(
+                FixtureTransport::new()
+                    .with_page(
+                        "http://fixtures/search/title/?title=star&start=1&count=1",
+                        page(1),
+                    )
+                    .with_page(
+                        "http://fixtures/search/title/?title=star&start=2&count=1",
+                        page(2),
+                    ),
+            )
+            .build()
+            .unwrap();
+
+        let items = engine
+            .search_stream(ByTitle::new(1, 1), "star", None)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|item| item.is_ok()));
+
+        let items = engine
+            .search_stream(ByTitle::new(1, 1), "star", Some(1))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_seasons_follows_season_picker() {
+        // The page without a season shows the latest one.
+        let page = |season: &str, selected: &str| {
+            format!(
+                r#"<select id="bySeason">
+                    <option value="1">1</option>
+                    <option value="2" {1}>2</option>
+                    <option value="-1">Unknown</option>
+                </select>
+                <div class="list detail eplist"><div class="list_item odd"><div class="info">
+                    <meta itemprop="episodeNumber" content="1"/>
+                    <strong><a href="/title/tt000000{0}/">Season {0} premiere</a></strong>
+                </div></div></div>"#,
+                season, selected
+            )
+        };
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://fixtures")
+            .transport(
+                FixtureTransport::new()
+                    .with_page(
+                        "http://fixtures/title/tt0944947/episodes",
+                        page("2", "selected"),
+                    )
+                    .with_page(
+                        "http://fixtures/title/tt0944947/episodes?season=1",
+                        page("1", ""),
+                    )
+                    .with_page(
+                        "http://fixtures/title/tt0944947/episodes?season=-1",
+                        page("3", ""),
+                    ),
+            )
+            .build()
+            .unwrap();
+
+        let seasons = engine.all_seasons("tt0944947").await.unwrap();
+        let numbers = seasons.iter().map(|s| s.season()).collect::<Vec<_>>();
+        assert_eq!(
+            numbers,
+            [
+                Some(SeasonNumber::Numbered(1)),
+                Some(SeasonNumber::Numbered(2)),
+                Some(SeasonNumber::Unknown)
+            ]
+        );
+        assert_eq!(
+            seasons[1].episodes()[0].get_title_id().as_deref(),
+            Some("tt0000002")
+        );
+
+        assert!(engine.all_seasons("nm0000434").await.is_err());
     }
 }
@@ -0,0 +1,189 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::error::ImdbError;
+
+/// Client side limits for requests sent by an engine and all of its clones.
+///
+/// Every attempt counts, retries included. Unset limits are not enforced.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimit {
+    per_second: Option<u32>,
+    per_minute: Option<u32>,
+    max_in_flight: Option<usize>,
+}
+
+impl RateLimit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn per_second(mut self, requests: u32) -> Self {
+        self.per_second = Some(requests);
+        self
+    }
+
+    pub fn per_minute(mut self, requests: u32) -> Self {
+        self.per_minute = Some(requests);
+        self
+    }
+
+    /// Requests allowed to wait for a response at the same time.
+    pub fn max_in_flight(mut self, requests: usize) -> Self {
+        self.max_in_flight = Some(requests);
+        self
+    }
+
+    pub(crate) fn build(&self) -> Result<RateLimiter, ImdbError> {
+        if self.per_second == Some(0) || self.per_minute == Some(0) || self.max_in_flight == Some(0)
+        {
+            return Err(ImdbError::InvalidConfig(
+                "rate limits must allow at least one request".to_string(),
+            ));
+        }
+
+        let mut buckets = vec![];
+        if let Some(requests) = self.per_second {
+            buckets.push(TokenBucket::new(requests, Duration::from_secs(1)));
+        }
+        if let Some(requests) = self.per_minute {
+            buckets.push(TokenBucket::new(requests, Duration::from_secs(60)));
+        }
+
+        Ok(RateLimiter {
+            buckets: Mutex::new(buckets),
+            in_flight: self
+                .max_in_flight
+                .map(|requests| Arc::new(Semaphore::new(requests))),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    per_second: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests: u32, period: Duration) -> Self {
+        Self {
+            capacity: requests as f64,
+            tokens: requests as f64,
+            per_second: requests as f64 / period.as_secs_f64(),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    fn wait(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.per_second)
+        }
+    }
+}
+
+/// Shared by every clone of an engine, see [`RateLimit`].
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    buckets: Mutex<Vec<TokenBucket>>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+/// Held while a request is in flight.
+pub(crate) struct RateLimitPermit {
+    _in_flight: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    pub(crate) fn unlimited() -> Self {
+        Self {
+            buckets: Mutex::new(vec![]),
+            in_flight: None,
+        }
+    }
+
+    /// Waits for a free slot in every bucket and in the in flight cap.
+    pub(crate) async fn acquire(&self) -> RateLimitPermit {
+        let in_flight = match &self.in_flight {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("rate limit semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let wait = Self::refill_and_wait(&mut buckets);
+                if wait.is_zero() {
+                    buckets.iter_mut().for_each(|bucket| bucket.tokens -= 1.0);
+                    break;
+                }
+                wait
+            };
+            tokio::time::sleep(wait).await;
+        }
+
+        RateLimitPermit {
+            _in_flight: in_flight,
+        }
+    }
+
+    pub(crate) fn time_until_next_slot(&self) -> Duration {
+        Self::refill_and_wait(&mut self.buckets.lock().unwrap())
+    }
+
+    fn refill_and_wait(buckets: &mut [TokenBucket]) -> Duration {
+        let now = Instant::now();
+        buckets
+            .iter_mut()
+            .map(|bucket| {
+                bucket.refill(now);
+                bucket.wait()
+            })
+            .max()
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use super::RateLimit;
+
+    #[tokio::test]
+    async fn test_bucket_runs_out_and_reports_wait() {
+        let limiter = RateLimit::new()
+            .per_second(2)
+            .per_minute(100)
+            .build()
+            .unwrap();
+
+        assert_eq!(limiter.time_until_next_slot(), Duration::ZERO);
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        let wait = limiter.time_until_next_slot();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_millis(500));
+
+        assert!(RateLimit::new().per_minute(0).build().is_err());
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::{HeaderMap, StatusCode, Uri};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use super::cache::fnv1a;
+use super::transport::{Response, Transport, TransportFuture};
+use crate::error::ImdbError;
+
+const RECORD_EXTENSION: &str = "record";
+
+/// Where recorded pages are written to and replayed from.
+#[derive(Debug, Clone)]
+pub enum RecordStore {
+    /// One file per uri, a later recording of the same uri replaces the older one.
+    Directory(PathBuf),
+    /// Every recording appended to a single file.
+    Archive(PathBuf),
+}
+
+/// A fetched page with everything needed to serve it again.
+///
+/// Stored as a few `key: value` lines, a blank line and the raw body:
+///
+/// ```text
+/// uri: https://www.imdb.com/find?s=tt&q=star
+/// status: 200
+/// timestamp: 1660000000
+/// header: content-type: text/html
+/// body-length: 1234
+///
+/// <html>...
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recording {
+    uri: String,
+    timestamp: SystemTime,
+    response: Response,
+}
+
+impl Recording {
+    pub fn uri(&self) -> &str {
+        self.uri.as_ref()
+    }
+
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let timestamp = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs();
+        let mut head = format!(
+            "uri: {}\nstatus: {}\ntimestamp: {}\n",
+            self.uri,
+            self.response.status().as_u16(),
+            timestamp
+        );
+        for (name, value) in self.response.headers() {
+            if let Ok(value) = value.to_str() {
+                head.push_str(&format!("header: {}: {}\n", name, value));
+            }
+        }
+        head.push_str(&format!("body-length: {}\n\n", self.response.body().len()));
+
+        let mut encoded = head.into_bytes();
+        encoded.extend_from_slice(self.response.body());
+        encoded.push(b'\n');
+        encoded
+    }
+
+    /// Decodes the recording at the start of `data`, returns it with the rest of `data`.
+    fn decode(data: &[u8]) -> Result<(Self, &[u8]), String> {
+        let head_end = data
+            .windows(2)
+            .position(|w| w == b"\n\n")
+            .ok_or("recording has no body separator")?;
+        let head = std::str::from_utf8(&data[..head_end]).map_err(|e| e.to_string())?;
+
+        let mut uri = None;
+        let mut status = None;
+        let mut timestamp = UNIX_EPOCH;
+        let mut headers = HeaderMap::new();
+        let mut body_length = None;
+        for line in head.lines() {
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| format!("malformed recording line `{}`", line))?;
+            match key {
+                "uri" => uri = Some(value.to_string()),
+                "status" => {
+                    status = Some(
+                        value
+                            .parse::<u16>()
+                            .ok()
+                            .and_then(|s| StatusCode::from_u16(s).ok())
+                            .ok_or_else(|| format!("bad status `{}`", value))?,
+                    )
+                }
+                "timestamp" => {
+                    let secs = value
+                        .parse::<u64>()
+                        .map_err(|_| format!("bad timestamp `{}`", value))?;
+                    timestamp = UNIX_EPOCH + Duration::from_secs(secs);
+                }
+                "header" => {
+                    let (name, value) = value
+                        .split_once(": ")
+                        .ok_or_else(|| format!("malformed header `{}`", value))?;
+                    headers.append(
+                        HeaderName::from_str(name).map_err(|e| e.to_string())?,
+                        HeaderValue::from_str(value).map_err(|e| e.to_string())?,
+                    );
+                }
+                "body-length" => {
+                    body_length = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| format!("bad body length `{}`", value))?,
+                    )
+                }
+                _ => return Err(format!("unknown recording key `{}`", key)),
+            }
+        }
+
+        let uri = uri.ok_or("recording has no uri")?;
+        let status = status.ok_or("recording has no status")?;
+        let body_length = body_length.ok_or("recording has no body length")?;
+        let body_start = head_end + 2;
+        let body_end = body_start + body_length;
+        if data.len() < body_end {
+            return Err(format!("recording of `{}` is truncated", uri));
+        }
+
+        let rest = &data[body_end..];
+        let rest = rest.strip_prefix(b"\n").unwrap_or(rest);
+        Ok((
+            Self {
+                uri,
+                timestamp,
+                response: Response::new(status, headers, data[body_start..body_end].to_vec()),
+            },
+            rest,
+        ))
+    }
+}
+
+/// Wraps another transport and writes every page it fetches to a [`RecordStore`].
+pub struct RecordingTransport<T> {
+    inner: T,
+    store: RecordStore,
+    // Keeps concurrent archive appends from interleaving.
+    write_lock: Mutex<()>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T, store: RecordStore) -> Self {
+        Self {
+            inner,
+            store,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    async fn write(&self, recording: &Recording) -> std::io::Result<()> {
+        let _guard = self.write_lock.lock().await;
+        match &self.store {
+            RecordStore::Directory(directory) => {
+                tokio::fs::create_dir_all(directory).await?;
+                // Renamed into place like the `DiskCache` pages, so a replay
+                // never loads a half written recording.
+                let path = record_path(directory, &recording.uri);
+                static WRITES: AtomicU64 = AtomicU64::new(0);
+                let temp = path.with_extension(format!(
+                    "{}-{}.tmp",
+                    std::process::id(),
+                    WRITES.fetch_add(1, Ordering::Relaxed)
+                ));
+
+                let written = match tokio::fs::write(&temp, recording.encode()).await {
+                    Ok(()) => tokio::fs::rename(&temp, &path).await,
+                    Err(e) => Err(e),
+                };
+                if written.is_err() {
+                    let _ = tokio::fs::remove_file(&temp).await;
+                }
+                written
+            }
+            RecordStore::Archive(path) => {
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                file.write_all(&recording.encode()).await?;
+                file.flush().await
+            }
+        }
+    }
+}
+
+impl<T: Transport> Transport for RecordingTransport<T> {
+    fn fetch(&self, uri: Uri) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let recording = Recording {
+                uri: uri.to_string(),
+                timestamp: SystemTime::now(),
+                response: self.inner.fetch(uri).await?,
+            };
+            // Not a transport error, retrying would fail to write again.
+            self.write(&recording).await.map_err(ImdbError::Record)?;
+
+            Ok(recording.response)
+        })
+    }
+}
+
+/// Serves pages from a [`RecordStore`] and never touches the network.
+///
+/// When a uri was recorded more than once the latest recording wins, an uri
+/// that was never recorded is a transport error.
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    recordings: HashMap<String, Recording>,
+}
+
+impl ReplayTransport {
+    pub fn load(store: &RecordStore) -> Result<Self, ImdbError> {
+        let mut recordings = HashMap::new();
+        let mut add = |data: &[u8]| -> Result<(), ImdbError> {
+            let mut rest = data;
+            while !rest.is_empty() {
+                let (recording, remaining) =
+                    Recording::decode(rest).map_err(ImdbError::InvalidConfig)?;
+                recordings.insert(recording.uri.clone(), recording);
+                rest = remaining;
+            }
+            Ok(())
+        };
+
+        match store {
+            RecordStore::Directory(directory) => {
+                let mut paths = fs::read_dir(directory)
+                    .map_err(invalid_store)?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| {
+                        path.extension().and_then(|e| e.to_str()) == Some(RECORD_EXTENSION)
+                    })
+                    .collect::<Vec<PathBuf>>();
+                paths.sort();
+                for path in paths {
+                    add(&fs::read(path).map_err(invalid_store)?)?;
+                }
+            }
+            RecordStore::Archive(path) => add(&fs::read(path).map_err(invalid_store)?)?,
+        }
+
+        Ok(Self { recordings })
+    }
+
+    pub fn recordings(&self) -> impl Iterator<Item = &Recording> {
+        self.recordings.values()
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn fetch(&self, uri: Uri) -> TransportFuture<'_> {
+        let response = match self.recordings.get(&uri.to_string()) {
+            Some(recording) => Ok(recording.response.clone()),
+            None => Err(ImdbError::transport(format!(
+                "no recorded response for `{}`",
+                uri
+            ))),
+        };
+        Box::pin(async move { response })
+    }
+}
+
+fn record_path(directory: &Path, uri: &str) -> PathBuf {
+    directory.join(format!("{:016x}.{}", fnv1a(uri), RECORD_EXTENSION))
+}
+
+fn invalid_store(error: std::io::Error) -> ImdbError {
+    ImdbError::InvalidConfig(format!("can not read recordings: {}", error))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::error::ImdbError;
+    use crate::prelude::{FixtureTransport, RetryPolicy};
+    use crate::search::by::ByTitleFind;
+    use crate::ImdbSearchEngine;
+
+    use super::RecordStore;
+
+    const FIND_PAGE: &str = r#"
+<div id="main"><div class="article"><div class="findSection"><table class="findList"><tbody>
+    <tr class="findResult odd">
+        <td class="primary_photo"><a href="/title/tt0076759/"><img src="https://m.media-amazon.com/images/M/star_wars.jpg"></a></td>
+        <td class="result_text"><a href="/title/tt0076759/">Star Wars</a> (1977)</td>
+    </tr>
+</tbody></table></div></div></div>
+"#;
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let root =
+            std::env::temp_dir().join(format!("imdb-swallower-record-{}", std::process::id()));
+        let stores = vec![
+            RecordStore::Directory(root.join("pages")),
+            RecordStore::Archive(root.join("pages.archive")),
+        ];
+
+        for store in stores {
+            let recorder = ImdbSearchEngine::builder()
+                .base_uri("http://fixtures")
+                .transport(
+                    FixtureTransport::new()
+                        .with_page("http://fixtures/find?s=tt&q=star", FIND_PAGE),
+                )
+                .record_to(store.clone())
+                .build()
+                .unwrap();
+            recorder
+                .search_by(ByTitleFind::default(), "star")
+                .await
+                .unwrap();
+            // Failed pages are recorded as well.
+            assert!(recorder
+                .search_by(ByTitleFind::default(), "missing")
+                .await
+                .is_err());
+            if let RecordStore::Directory(directory) = &store {
+                // Only the two renamed recordings are left behind.
+                assert_eq!(std::fs::read_dir(directory).unwrap().count(), 2);
+            }
+
+            let replayer = ImdbSearchEngine::builder()
+                .base_uri("http://fixtures")
+                .replay_from(store)
+                .build()
+                .unwrap();
+            let result = replayer
+                .search_by(ByTitleFind::default(), "star")
+                .await
+                .unwrap();
+            assert_eq!(result.items()[0].title(), "Star Wars");
+            assert!(replayer
+                .search_by(ByTitleFind::default(), "missing")
+                .await
+                .is_err());
+            assert!(replayer
+                .search_by(ByTitleFind::default(), "never recorded")
+                .await
+                .is_err());
+        }
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[tokio::test]
+    async fn test_failed_recording_is_not_retried() {
+        // A file where the store expects a directory.
+        let file =
+            std::env::temp_dir().join(format!("imdb-swallower-not-a-dir-{}", std::process::id()));
+        std::fs::write(&file, "").unwrap();
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://fixtures")
+            .transport(
+                FixtureTransport::new().with_page("http://fixtures/find?s=tt&q=star", FIND_PAGE),
+            )
+            .record_to(RecordStore::Directory(file.join("pages")))
+            .retry_policy(RetryPolicy::default().on_retry(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            engine.search_by(ByTitleFind::default(), "star").await,
+            Err(ImdbError::Record(_))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 0);
+
+        let _ = std::fs::remove_file(file);
+    }
+}
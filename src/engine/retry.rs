@@ -0,0 +1,264 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::{StatusCode, Uri};
+
+use crate::error::ImdbError;
+
+/// What the retry hook gets to see before the engine sleeps and tries again.
+#[derive(Debug)]
+pub struct RetryAttempt<'a> {
+    uri: &'a Uri,
+    attempt: u32,
+    error: &'a ImdbError,
+    delay: Duration,
+}
+
+impl<'a> RetryAttempt<'a> {
+    pub fn uri(&self) -> &Uri {
+        self.uri
+    }
+
+    /// The attempt that just failed, starting at `1`.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn error(&self) -> &ImdbError {
+        self.error
+    }
+
+    /// How long the engine waits before the next attempt.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+}
+
+type RetryHook = Arc<dyn Fn(&RetryAttempt) + Send + Sync>;
+
+/// When and how often `search_by` tries a request again.
+///
+/// The delay before attempt `n + 1` is `base_delay * 2^(n - 1)`, capped at
+/// `max_delay` and reduced by up to `jitter` of itself. A `Retry-After`
+/// header sent with the failed response replaces the computed delay, and the
+/// request is given up on when it asks to wait longer than `max_delay`.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retryable_statuses: Vec<StatusCode>,
+    retry_transport_errors: bool,
+    retry_rate_limited: bool,
+    respect_retry_after: bool,
+    on_retry: Option<RetryHook>,
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("retryable_statuses", &self.retryable_statuses)
+            .field("retry_transport_errors", &self.retry_transport_errors)
+            .field("retry_rate_limited", &self.retry_rate_limited)
+            .field("respect_retry_after", &self.respect_retry_after)
+            .field("on_retry", &self.on_retry.is_some())
+            .finish()
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts on connection errors, `429` and `5xx` gateway errors.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+            retryable_statuses: vec![
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_transport_errors: true,
+            retry_rate_limited: true,
+            respect_retry_after: true,
+            on_retry: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A single attempt, what the engine does unless told otherwise.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Fraction of the delay, between `0.0` and `1.0`, that is randomly taken off.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn retryable_statuses(mut self, statuses: Vec<StatusCode>) -> Self {
+        self.retryable_statuses = statuses;
+        self
+    }
+
+    pub fn retry_transport_errors(mut self, retry: bool) -> Self {
+        self.retry_transport_errors = retry;
+        self
+    }
+
+    pub fn retry_rate_limited(mut self, retry: bool) -> Self {
+        self.retry_rate_limited = retry;
+        self
+    }
+
+    pub fn respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Called once per failed attempt that is going to be retried.
+    pub fn on_retry(mut self, hook: impl Fn(&RetryAttempt) + Send + Sync + 'static) -> Self {
+        self.on_retry = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn is_retryable(&self, error: &ImdbError) -> bool {
+        match error {
+            ImdbError::Transport(_) => self.retry_transport_errors,
+            ImdbError::RateLimited { .. } => self.retry_rate_limited,
+            ImdbError::HttpStatus { status, .. } => self.retryable_statuses.contains(status),
+            _ => false,
+        }
+    }
+
+    /// The delay before retrying after `attempt` failed with `error`,
+    /// `None` if it should not be retried.
+    pub(crate) fn next_delay(&self, attempt: u32, error: &ImdbError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+
+        if self.respect_retry_after {
+            if let Some(retry_after) = error.retry_after() {
+                // Retrying sooner than asked would only be refused again.
+                return Some(retry_after).filter(|delay| *delay <= self.max_delay);
+            }
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        Some(backoff.mul_f64(1.0 - self.jitter * random_fraction()))
+    }
+
+    pub(crate) fn notify(&self, uri: &Uri, attempt: u32, error: &ImdbError, delay: Duration) {
+        if let Some(hook) = &self.on_retry {
+            hook(&RetryAttempt {
+                uri,
+                attempt,
+                error,
+                delay,
+            });
+        }
+    }
+}
+
+/// A number in `[0, 1)`, good enough to spread retries apart.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use hyper::StatusCode;
+
+    use crate::error::ImdbError;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn test_backoff_is_capped_and_respects_retry_after() {
+        let policy = RetryPolicy::default()
+            .max_attempts(10)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(0.0);
+        let unavailable = ImdbError::HttpStatus {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            retry_after: None,
+        };
+
+        assert_eq!(
+            policy.next_delay(1, &unavailable),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.next_delay(3, &unavailable),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(
+            policy.next_delay(6, &unavailable),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(policy.next_delay(10, &unavailable), None);
+
+        let rate_limited = ImdbError::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+        };
+        assert_eq!(
+            policy.next_delay(1, &rate_limited),
+            Some(Duration::from_secs(3))
+        );
+        // Longer than `max_delay`, the error goes back to the caller instead.
+        let rate_limited = ImdbError::RateLimited {
+            retry_after: Some(Duration::from_secs(42)),
+        };
+        assert_eq!(policy.next_delay(1, &rate_limited), None);
+        assert_eq!(
+            policy
+                .clone()
+                .max_delay(Duration::from_secs(60))
+                .next_delay(1, &rate_limited),
+            Some(Duration::from_secs(42))
+        );
+
+        let not_found = ImdbError::HttpStatus {
+            status: StatusCode::NOT_FOUND,
+            retry_after: None,
+        };
+        assert_eq!(policy.next_delay(1, &not_found), None);
+    }
+}
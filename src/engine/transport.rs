@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::body::{Bytes, HttpBody};
+use hyper::{client::HttpConnector, Body, Client, HeaderMap, Request, StatusCode, Uri};
+use hyper_tls::HttpsConnector;
+
+use crate::error::ImdbError;
+
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Response, ImdbError>> + Send + 'a>>;
+
+/// A fetched page, before any parsing happens.
+#[derive(Debug, Clone)]
+pub struct Response {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl Response {
+    pub fn new(status: StatusCode, headers: HeaderMap, body: impl Into<Bytes>) -> Self {
+        Self {
+            status,
+            headers,
+            body: body.into(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+}
+
+/// Whatever `ImdbSearchEngine::search_by` uses to get a page from an uri.
+pub trait Transport: Send + Sync {
+    fn fetch(&self, uri: Uri) -> TransportFuture<'_>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn fetch(&self, uri: Uri) -> TransportFuture<'_> {
+        (**self).fetch(uri)
+    }
+}
+
+/// The default transport, a hyper client speaking both http and https.
+#[derive(Debug, Clone)]
+pub struct HyperTransport {
+    hyper_client: Client<HttpsConnector<HttpConnector>>,
+    default_headers: HeaderMap,
+    max_response_size: Option<usize>,
+}
+
+impl HyperTransport {
+    pub(crate) fn new(
+        connect_timeout: Option<Duration>,
+        default_headers: HeaderMap,
+        max_response_size: Option<usize>,
+    ) -> Self {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_connect_timeout(connect_timeout);
+        let https = HttpsConnector::new_with_connector(http);
+        let hyper_client = Client::builder().build::<_, hyper::Body>(https);
+
+        Self {
+            hyper_client,
+            default_headers,
+            max_response_size,
+        }
+    }
+
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    async fn get(&self, uri: Uri) -> Result<Response, ImdbError> {
+        let mut request = Request::get(uri)
+            .body(Body::empty())
+            .map_err(|e| ImdbError::InvalidQuery(e.to_string()))?;
+        request.headers_mut().extend(self.default_headers.clone());
+
+        let mut resp = self
+            .hyper_client
+            .request(request)
+            .await
+            .map_err(ImdbError::transport)?;
+        let mut body = vec![];
+        while let Some(chunk) = resp.body_mut().data().await {
+            body.extend_from_slice(&chunk.map_err(ImdbError::transport)?);
+            if let Some(max) = self.max_response_size {
+                if body.len() > max {
+                    return Err(ImdbError::transport(format!(
+                        "response body exceeds {} bytes",
+                        max
+                    )));
+                }
+            }
+        }
+
+        Ok(Response::new(resp.status(), resp.headers().clone(), body))
+    }
+}
+
+impl Transport for HyperTransport {
+    fn fetch(&self, uri: Uri) -> TransportFuture<'_> {
+        Box::pin(self.get(uri))
+    }
+}
+
+/// Serves canned pages from memory, unknown uris get an empty `404`.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureTransport {
+    responses: HashMap<String, Response>,
+}
+
+impl FixtureTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves `html` with status `200` for `uri`.
+    pub fn with_page(self, uri: impl Into<String>, html: impl Into<String>) -> Self {
+        self.with_response(
+            uri,
+            Response::new(StatusCode::OK, HeaderMap::new(), html.into()),
+        )
+    }
+
+    pub fn with_response(mut self, uri: impl Into<String>, response: Response) -> Self {
+        self.responses.insert(uri.into(), response);
+        self
+    }
+}
+
+impl Transport for FixtureTransport {
+    fn fetch(&self, uri: Uri) -> TransportFuture<'_> {
+        let response = self
+            .responses
+            .get(&uri.to_string())
+            .cloned()
+            .unwrap_or_else(|| Response::new(StatusCode::NOT_FOUND, HeaderMap::new(), ""));
+        Box::pin(async move { Ok(response) })
+    }
+}
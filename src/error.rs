@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt;
+use std::string::FromUtf8Error;
+use std::time::Duration;
+
+use hyper::StatusCode;
+use scraper::ElementRef;
+
+const SNIPPET_LEN: usize = 200;
+
+/// Everything that can go wrong between building a query and getting parsed results.
+#[derive(Debug)]
+pub enum ImdbError {
+    /// The request never got a response: connection, tls, timeout or body errors.
+    Transport(Box<dyn Error + Send + Sync>),
+    /// A response came back with a non-2xx status other than `429`.
+    HttpStatus {
+        status: StatusCode,
+        retry_after: Option<Duration>,
+    },
+    /// The body is not valid utf-8.
+    Decode(FromUtf8Error),
+    /// The page does not look like what the parser expects.
+    Parse(ParseError),
+    /// IMDb answered `429 Too Many Requests`.
+    RateLimited { retry_after: Option<Duration> },
+    /// The query can not be turned into a valid request.
+    InvalidQuery(String),
+    /// The engine builder got an option it can not use.
+    InvalidConfig(String),
+    /// A response came back but could not be written to the record store.
+    Record(std::io::Error),
+}
+
+impl ImdbError {
+    pub(crate) fn transport(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self::Transport(error.into())
+    }
+
+    /// How long the server asked us to wait before trying again, if it did.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::HttpStatus { retry_after, .. } | Self::RateLimited { retry_after } => {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ImdbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(error) => write!(f, "transport error: {}", error),
+            Self::HttpStatus { status, .. } => write!(f, "unexpected http status: {}", status),
+            Self::Decode(error) => write!(f, "can not decode response body: {}", error),
+            Self::Parse(error) => write!(f, "can not parse page: {}", error),
+            Self::RateLimited { .. } => write!(f, "rate limited by the server"),
+            Self::InvalidQuery(message) => write!(f, "invalid query: {}", message),
+            Self::InvalidConfig(message) => write!(f, "invalid engine configuration: {}", message),
+            Self::Record(error) => write!(f, "can not record response: {}", error),
+        }
+    }
+}
+
+impl Error for ImdbError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Transport(error) => Some(error.as_ref()),
+            Self::Decode(error) => Some(error),
+            Self::Parse(error) => Some(error),
+            Self::Record(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FromUtf8Error> for ImdbError {
+    fn from(error: FromUtf8Error) -> Self {
+        Self::Decode(error)
+    }
+}
+
+impl From<ParseError> for ImdbError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+/// A selector that matched nothing, or matched an element missing what we need.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    selector: String,
+    snippet: String,
+}
+
+impl ParseError {
+    /// `element` is where `selector` was looked up.
+    pub(crate) fn new(selector: impl Into<String>, element: &ElementRef) -> Self {
+        let html = element.html();
+        let snippet = match html.char_indices().nth(SNIPPET_LEN) {
+            Some((end, _)) => format!("{}...", &html[..end]),
+            None => html,
+        };
+
+        Self {
+            selector: selector.into(),
+            snippet,
+        }
+    }
+
+    pub fn selector(&self) -> &str {
+        self.selector.as_ref()
+    }
+
+    /// The start of the offending element's html.
+    pub fn snippet(&self) -> &str {
+        self.snippet.as_ref()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "nothing matches `{}` in `{}`",
+            self.selector, self.snippet
+        )
+    }
+}
+
+impl Error for ParseError {}
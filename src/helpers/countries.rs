@@ -0,0 +1,241 @@
+/// English country names as IMDb writes them, with their ISO 3166 alpha-2
+/// code. Countries that no longer exist, like `West Germany`, have none.
+const COUNTRIES: &[(&str, &str)] = &[
+    ("Afghanistan", "af"),
+    ("Åland Islands", "ax"),
+    ("Albania", "al"),
+    ("Algeria", "dz"),
+    ("American Samoa", "as"),
+    ("Andorra", "ad"),
+    ("Angola", "ao"),
+    ("Anguilla", "ai"),
+    ("Antarctica", "aq"),
+    ("Antigua and Barbuda", "ag"),
+    ("Argentina", "ar"),
+    ("Armenia", "am"),
+    ("Aruba", "aw"),
+    ("Australia", "au"),
+    ("Austria", "at"),
+    ("Azerbaijan", "az"),
+    ("Bahamas", "bs"),
+    ("Bahrain", "bh"),
+    ("Bangladesh", "bd"),
+    ("Barbados", "bb"),
+    ("Belarus", "by"),
+    ("Belgium", "be"),
+    ("Belize", "bz"),
+    ("Benin", "bj"),
+    ("Bermuda", "bm"),
+    ("Bhutan", "bt"),
+    ("Bolivia", "bo"),
+    ("Bosnia and Herzegovina", "ba"),
+    ("Botswana", "bw"),
+    ("Brazil", "br"),
+    ("British Virgin Islands", "vg"),
+    ("Brunei", "bn"),
+    ("Brunei Darussalam", "bn"),
+    ("Bulgaria", "bg"),
+    ("Burkina Faso", "bf"),
+    ("Burundi", "bi"),
+    ("Cambodia", "kh"),
+    ("Cameroon", "cm"),
+    ("Canada", "ca"),
+    ("Cape Verde", "cv"),
+    ("Cayman Islands", "ky"),
+    ("Central African Republic", "cf"),
+    ("Chad", "td"),
+    ("Chile", "cl"),
+    ("China", "cn"),
+    ("Colombia", "co"),
+    ("Comoros", "km"),
+    ("Congo", "cg"),
+    ("Cook Islands", "ck"),
+    ("Costa Rica", "cr"),
+    ("Côte d'Ivoire", "ci"),
+    ("Croatia", "hr"),
+    ("Cuba", "cu"),
+    ("Curaçao", "cw"),
+    ("Cyprus", "cy"),
+    ("Czech Republic", "cz"),
+    ("Czechia", "cz"),
+    ("Democratic Republic of the Congo", "cd"),
+    ("Denmark", "dk"),
+    ("Djibouti", "dj"),
+    ("Dominica", "dm"),
+    ("Dominican Republic", "do"),
+    ("Ecuador", "ec"),
+    ("Egypt", "eg"),
+    ("El Salvador", "sv"),
+    ("Equatorial Guinea", "gq"),
+    ("Eritrea", "er"),
+    ("Estonia", "ee"),
+    ("Eswatini", "sz"),
+    ("Ethiopia", "et"),
+    ("Faroe Islands", "fo"),
+    ("Fiji", "fj"),
+    ("Finland", "fi"),
+    ("France", "fr"),
+    ("French Guiana", "gf"),
+    ("French Polynesia", "pf"),
+    ("Gabon", "ga"),
+    ("Gambia", "gm"),
+    ("Georgia", "ge"),
+    ("Germany", "de"),
+    ("Ghana", "gh"),
+    ("Gibraltar", "gi"),
+    ("Greece", "gr"),
+    ("Greenland", "gl"),
+    ("Grenada", "gd"),
+    ("Guadeloupe", "gp"),
+    ("Guam", "gu"),
+    ("Guatemala", "gt"),
+    ("Guinea", "gn"),
+    ("Guinea-Bissau", "gw"),
+    ("Guyana", "gy"),
+    ("Haiti", "ht"),
+    ("Honduras", "hn"),
+    ("Hong Kong", "hk"),
+    ("Hungary", "hu"),
+    ("Iceland", "is"),
+    ("India", "in"),
+    ("Indonesia", "id"),
+    ("Iran", "ir"),
+    ("Iraq", "iq"),
+    ("Ireland", "ie"),
+    ("Isle of Man", "im"),
+    ("Israel", "il"),
+    ("Italy", "it"),
+    ("Ivory Coast", "ci"),
+    ("Jamaica", "jm"),
+    ("Japan", "jp"),
+    ("Jordan", "jo"),
+    ("Kazakhstan", "kz"),
+    ("Kenya", "ke"),
+    ("Kiribati", "ki"),
+    ("Kosovo", "xk"),
+    ("Kuwait", "kw"),
+    ("Kyrgyzstan", "kg"),
+    ("Laos", "la"),
+    ("Latvia", "lv"),
+    ("Lebanon", "lb"),
+    ("Lesotho", "ls"),
+    ("Liberia", "lr"),
+    ("Libya", "ly"),
+    ("Liechtenstein", "li"),
+    ("Lithuania", "lt"),
+    ("Luxembourg", "lu"),
+    ("Macao", "mo"),
+    ("Macau", "mo"),
+    ("Madagascar", "mg"),
+    ("Malawi", "mw"),
+    ("Malaysia", "my"),
+    ("Maldives", "mv"),
+    ("Mali", "ml"),
+    ("Malta", "mt"),
+    ("Marshall Islands", "mh"),
+    ("Martinique", "mq"),
+    ("Mauritania", "mr"),
+    ("Mauritius", "mu"),
+    ("Mexico", "mx"),
+    ("Micronesia", "fm"),
+    ("Moldova", "md"),
+    ("Monaco", "mc"),
+    ("Mongolia", "mn"),
+    ("Montenegro", "me"),
+    ("Morocco", "ma"),
+    ("Mozambique", "mz"),
+    ("Myanmar", "mm"),
+    ("Namibia", "na"),
+    ("Nauru", "nr"),
+    ("Nepal", "np"),
+    ("Netherlands", "nl"),
+    ("New Caledonia", "nc"),
+    ("New Zealand", "nz"),
+    ("Nicaragua", "ni"),
+    ("Niger", "ne"),
+    ("Nigeria", "ng"),
+    ("North Korea", "kp"),
+    ("North Macedonia", "mk"),
+    ("Norway", "no"),
+    ("Oman", "om"),
+    ("Pakistan", "pk"),
+    ("Palau", "pw"),
+    ("Palestine", "ps"),
+    ("Panama", "pa"),
+    ("Papua New Guinea", "pg"),
+    ("Paraguay", "py"),
+    ("Peru", "pe"),
+    ("Philippines", "ph"),
+    ("Poland", "pl"),
+    ("Portugal", "pt"),
+    ("Puerto Rico", "pr"),
+    ("Qatar", "qa"),
+    ("Republic of North Macedonia", "mk"),
+    ("Réunion", "re"),
+    ("Romania", "ro"),
+    ("Russia", "ru"),
+    ("Rwanda", "rw"),
+    ("Saint Kitts and Nevis", "kn"),
+    ("Saint Lucia", "lc"),
+    ("Saint Vincent and the Grenadines", "vc"),
+    ("Samoa", "ws"),
+    ("San Marino", "sm"),
+    ("Sao Tome and Principe", "st"),
+    ("Saudi Arabia", "sa"),
+    ("Senegal", "sn"),
+    ("Serbia", "rs"),
+    ("Seychelles", "sc"),
+    ("Sierra Leone", "sl"),
+    ("Singapore", "sg"),
+    ("Slovakia", "sk"),
+    ("Slovenia", "si"),
+    ("Solomon Islands", "sb"),
+    ("Somalia", "so"),
+    ("South Africa", "za"),
+    ("South Korea", "kr"),
+    ("South Sudan", "ss"),
+    ("Spain", "es"),
+    ("Sri Lanka", "lk"),
+    ("Sudan", "sd"),
+    ("Suriname", "sr"),
+    ("Swaziland", "sz"),
+    ("Sweden", "se"),
+    ("Switzerland", "ch"),
+    ("Syria", "sy"),
+    ("Taiwan", "tw"),
+    ("Tajikistan", "tj"),
+    ("Tanzania", "tz"),
+    ("Thailand", "th"),
+    ("Timor-Leste", "tl"),
+    ("Togo", "tg"),
+    ("Tonga", "to"),
+    ("Trinidad and Tobago", "tt"),
+    ("Tunisia", "tn"),
+    ("Turkey", "tr"),
+    ("Türkiye", "tr"),
+    ("Turkmenistan", "tm"),
+    ("Tuvalu", "tv"),
+    ("Uganda", "ug"),
+    ("Ukraine", "ua"),
+    ("United Arab Emirates", "ae"),
+    ("United Kingdom", "gb"),
+    ("United States", "us"),
+    ("Uruguay", "uy"),
+    ("Uzbekistan", "uz"),
+    ("Vanuatu", "vu"),
+    ("Vatican", "va"),
+    ("Venezuela", "ve"),
+    ("Vietnam", "vn"),
+    ("Yemen", "ye"),
+    ("Zambia", "zm"),
+    ("Zimbabwe", "zw"),
+];
+
+/// The lowercase code of a country named like `United States`.
+pub(crate) fn country_code_of(name: &str) -> Option<&'static str> {
+    let name = name.trim();
+    COUNTRIES
+        .iter()
+        .find(|(country, _)| country.eq_ignore_ascii_case(name))
+        .map(|(_, code)| *code)
+}
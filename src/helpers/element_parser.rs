@@ -1,9 +1,14 @@
 use scraper::ElementRef;
 
+use crate::error::ParseError;
+
 use super::helper_tags::ATag;
 
 pub(crate) trait ElementParserHelper {
     fn parse_a_tag(&self) -> Option<ATag>;
+    fn require_a_tag(&self) -> Result<ATag, ParseError>;
+    fn require_attr(&self, name: &str) -> Result<&str, ParseError>;
+    fn text_content(&self) -> String;
 }
 
 impl<'a> ElementParserHelper for ElementRef<'a> {
@@ -14,4 +19,23 @@ impl<'a> ElementParserHelper for ElementRef<'a> {
 
         Some(ATag { text, link })
     }
+
+    fn require_a_tag(&self) -> Result<ATag, ParseError> {
+        self.parse_a_tag()
+            .ok_or_else(|| ParseError::new("a[href]", self))
+    }
+
+    fn require_attr(&self, name: &str) -> Result<&str, ParseError> {
+        self.value()
+            .attr(name)
+            .ok_or_else(|| ParseError::new(format!("[{}]", name), self))
+    }
+
+    /// All text inside the element, with runs of whitespace collapsed.
+    fn text_content(&self) -> String {
+        self.text()
+            .flat_map(|t| t.split_whitespace())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
 }
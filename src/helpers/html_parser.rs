@@ -1,30 +1,43 @@
 use scraper::{ElementRef, Html};
 
+use crate::error::ParseError;
+
 use super::get_selector;
 
 pub(crate) trait HtmlParserHelper {
-    fn select_first(&self, selector: &str) -> Option<ElementRef>;
-    fn select_all(&self, selector: &str) -> Vec<ElementRef>;
+    fn select_first(&self, selector: &str) -> Option<ElementRef<'_>>;
+    fn select_all(&self, selector: &str) -> Vec<ElementRef<'_>>;
+    fn require_first(&self, selector: &str) -> Result<ElementRef<'_>, ParseError>;
 }
 
 impl HtmlParserHelper for Html {
-    fn select_first(&self, selector: &str) -> Option<ElementRef> {
+    fn select_first(&self, selector: &str) -> Option<ElementRef<'_>> {
         self.select(&get_selector(selector)).next()
     }
 
-    fn select_all(&self, selector: &str) -> Vec<ElementRef> {
+    fn select_all(&self, selector: &str) -> Vec<ElementRef<'_>> {
         self.select(&get_selector(selector))
             .collect::<Vec<ElementRef>>()
     }
+
+    fn require_first(&self, selector: &str) -> Result<ElementRef<'_>, ParseError> {
+        self.select_first(selector)
+            .ok_or_else(|| ParseError::new(selector, &self.root_element()))
+    }
 }
 
 impl<'a> HtmlParserHelper for ElementRef<'a> {
-    fn select_first(&self, selector: &str) -> Option<ElementRef> {
+    fn select_first(&self, selector: &str) -> Option<ElementRef<'_>> {
         self.select(&get_selector(selector)).next()
     }
 
-    fn select_all(&self, selector: &str) -> Vec<ElementRef> {
+    fn select_all(&self, selector: &str) -> Vec<ElementRef<'_>> {
         self.select(&get_selector(selector))
             .collect::<Vec<ElementRef>>()
     }
+
+    fn require_first(&self, selector: &str) -> Result<ElementRef<'_>, ParseError> {
+        self.select_first(selector)
+            .ok_or_else(|| ParseError::new(selector, self))
+    }
 }
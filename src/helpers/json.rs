@@ -0,0 +1,50 @@
+use scraper::Html;
+use serde_json::Value;
+
+use super::html_parser::HtmlParserHelper;
+
+/// The `application/ld+json` block IMDb embeds for search engines.
+pub(crate) fn ld_json(html: &Html) -> Option<Value> {
+    script_json(html, r#"script[type="application/ld+json"]"#)
+}
+
+/// The page state of IMDb's Next.js frontend.
+pub(crate) fn next_data(html: &Html) -> Option<Value> {
+    script_json(html, "script#__NEXT_DATA__")
+}
+
+fn script_json(html: &Html, selector: &str) -> Option<Value> {
+    let script = html.select_first(selector)?;
+    serde_json::from_str(&script.text().collect::<String>()).ok()
+}
+
+/// A non empty string, with the html entities IMDb leaves in JSON-LD decoded.
+pub(crate) fn json_str(value: &Value) -> Option<String> {
+    let text = value
+        .as_str()?
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&");
+    let text = text.trim();
+
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Numbers are sometimes strings, like `"1,234"`.
+pub(crate) fn json_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.replace(',', "").trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A single value or an array of them, as JSON-LD allows both.
+pub(crate) fn json_list(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(Value::Null) | None => vec![],
+        Some(value) => vec![value],
+    }
+}
@@ -1,9 +1,52 @@
+pub mod countries;
 pub mod element_parser;
 pub mod helper_tags;
 pub mod html_parser;
+pub mod json;
 
 use scraper::Selector;
 
+use crate::error::ParseError;
+
 pub(crate) fn get_selector(selector: &str) -> Selector {
     Selector::parse(selector).unwrap()
 }
+
+/// The id in a site relative link, `tt0076759` in `/title/tt0076759/?ref_=fn`.
+pub(crate) fn link_id(link: &str) -> Option<String> {
+    link.split('/')
+        .nth(2)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+}
+
+/// Every `(...)` group of `text`, like `(Oakland, California) (premiere)`.
+pub(crate) fn parenthesised(text: &str) -> Vec<String> {
+    text.split('(')
+        .skip(1)
+        .filter_map(|group| Some(group.split_once(')')?.0.trim().to_string()))
+        .filter(|group| !group.is_empty())
+        .collect()
+}
+
+/// Splits per item parse results into items and warnings, the one place the
+/// `lenient` option of the list parsers is applied (see [`crate::search::By`]).
+///
+/// Strict parsers fail on the first malformed item, lenient ones skip it and
+/// keep its error as a warning, so `warnings` is always empty when strict.
+pub(crate) fn collect_items<T>(
+    lenient: bool,
+    results: impl IntoIterator<Item = Result<T, ParseError>>,
+) -> Result<(Vec<T>, Vec<ParseError>), ParseError> {
+    let mut items = vec![];
+    let mut warnings = vec![];
+    for result in results {
+        match result {
+            Ok(item) => items.push(item),
+            Err(error) if lenient => warnings.push(error),
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok((items, warnings))
+}
@@ -1,6 +1,7 @@
 use engine::ImdbSearchEngine;
 
 mod engine;
+mod error;
 mod helpers;
 pub mod prelude;
 mod search;
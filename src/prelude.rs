@@ -1,3 +1,14 @@
+pub use super::engine::cache::{CacheBackend, CacheFuture, DiskCache, MemoryCache};
+pub use super::engine::rate_limit::RateLimit;
+pub use super::engine::record::{RecordStore, Recording, RecordingTransport, ReplayTransport};
+pub use super::engine::retry::{RetryAttempt, RetryPolicy};
+pub use super::engine::transport::{
+    FixtureTransport, HyperTransport, Response, Transport, TransportFuture,
+};
 pub use super::engine::ImdbSearchEngine;
+pub use super::engine::ImdbSearchEngineBuilder;
+pub use super::error::{ImdbError, ParseError};
 pub use super::search::by;
+pub use super::search::filters;
 pub use super::search::results;
+pub use super::search::{By, Paginated};
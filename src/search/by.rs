@@ -1,2 +1,17 @@
-pub use super::by_title::ByTitle;
+pub use super::by_character::ByCharacter;
+pub use super::by_company::ByCompany;
+pub use super::by_find_all::ByFindAll;
+pub use super::by_full_credits::ByFullCredits;
+pub use super::by_keyword::ByKeyword;
+pub use super::by_name::ByName;
+pub use super::by_name_id::ByNameId;
+pub use super::by_name_search::{ByNameSearch, ByNameSearchBuilder};
+pub use super::by_parents_guide::ByParentsGuide;
+pub use super::by_ratings::ByRatings;
+pub use super::by_release_info::ByReleaseInfo;
+pub use super::by_reviews::ByReviews;
+pub use super::by_season::BySeason;
+pub use super::by_technical_specs::ByTechnicalSpecs;
+pub use super::by_title::{ByTitle, ByTitleBuilder};
 pub use super::by_title_find::ByTitleFind;
+pub use super::by_title_id::ByTitleId;
@@ -0,0 +1,155 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper, link_id,
+};
+use crate::ImdbSearchEngine;
+
+use super::find::{find_rows, find_uri, FindRow};
+use super::By;
+
+/// Finds characters by name, `/find?s=ch`.
+pub struct ByCharacter {
+    lenient: bool,
+}
+
+impl Default for ByCharacter {
+    fn default() -> Self {
+        Self { lenient: true }
+    }
+}
+
+impl ByCharacter {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub(crate) fn parse_row(tr: ElementRef) -> Result<ByCharacterFoundItem, ParseError> {
+        let row = FindRow::parse(tr)?;
+        let a_tag = row.main_link()?;
+
+        // <small>(as Mark Hamill in <a href="/title/tt0076759/">Star Wars</a> (1977))</small>
+        let title = row
+            .text
+            .select_first("small a")
+            .and_then(|a| a.parse_a_tag())
+            .map(|a| ATag {
+                text: a.text.trim().to_string(),
+                link: a.link,
+            });
+
+        Ok(ByCharacterFoundItem {
+            name: a_tag.text.trim().to_string(),
+            link: a_tag.link,
+            title,
+        })
+    }
+}
+
+pub struct ByCharacterFoundItem {
+    name: String,
+    link: String,
+    title: Option<ATag>,
+}
+
+impl ByCharacterFoundItem {
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Like `/title/tt0076759/characters/nm0000434`.
+    pub fn link(&self) -> &str {
+        self.link.as_ref()
+    }
+
+    /// The title the character appears in.
+    pub fn title(&self) -> Option<&ATag> {
+        self.title.as_ref()
+    }
+
+    pub fn get_title_id(&self) -> Option<String> {
+        link_id(&self.title.as_ref()?.link)
+    }
+}
+
+pub struct ByCharacterFound {
+    items: Vec<ByCharacterFoundItem>,
+    warnings: Vec<ParseError>,
+}
+
+impl ByCharacterFound {
+    pub fn items(&self) -> &[ByCharacterFoundItem] {
+        self.items.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByCharacter {
+    type ParseResult = ByCharacterFound;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        find_uri(engine, "ch", query)
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let rows = find_rows(&html).into_iter().map(ByCharacter::parse_row);
+
+        let (items, warnings) = collect_items(self.lenient, rows)?;
+        Ok(ByCharacterFound { items, warnings })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::ByCharacter;
+
+    #[test]
+    pub fn test_by_character_parser() {
+        let html = Html::parse_fragment(
+            r#"
+<div id="main">
+    <div class="article">
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="ch"></a>Characters</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="primary_photo"> <a href="/title/tt0076759/characters/nm0000434?ref_=fn_ch_1"><img
+                                    src="https://m.media-amazon.com/images/M/luke.jpg"></a> </td>
+                        <td class="result_text"> <a href="/title/tt0076759/characters/nm0000434?ref_=fn_ch_1">Luke Skywalker</a>
+                            <small>(as Mark Hamill in <a href="/title/tt0076759/?ref_=fn_ch_1a"> Star Wars</a> (1977))</small> </td>
+                    </tr>
+                    <tr class="findResult even">
+                        <td class="primary_photo"> <a href="/title/tt0120915/characters/nm0000204?ref_=fn_ch_2"><img
+                                    src="https://m.media-amazon.com/images/M/padme.jpg"></a> </td>
+                        <td class="result_text"> <a href="/title/tt0120915/characters/nm0000204?ref_=fn_ch_2">Padmé</a> </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+    </div>
+</div>
+        "#,
+        );
+
+        let result = ByCharacter::default().parse_result(html).unwrap();
+        assert_eq!(result.items().len(), 2);
+
+        let first = &result.items()[0];
+        assert_eq!(first.name(), "Luke Skywalker");
+        assert_eq!(first.title().unwrap().text(), "Star Wars");
+        assert_eq!(first.get_title_id().as_deref(), Some("tt0076759"));
+        assert!(result.items()[1].title().is_none());
+    }
+}
@@ -0,0 +1,144 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{collect_items, link_id};
+use crate::ImdbSearchEngine;
+
+use super::find::{find_rows, find_uri, FindRow};
+use super::By;
+
+/// Finds production companies, distributors, ... by name, `/find?s=co`.
+pub struct ByCompany {
+    lenient: bool,
+}
+
+impl Default for ByCompany {
+    fn default() -> Self {
+        Self { lenient: true }
+    }
+}
+
+impl ByCompany {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub(crate) fn parse_row(tr: ElementRef) -> Result<ByCompanyFoundItem, ParseError> {
+        let row = FindRow::parse(tr)?;
+        let a_tag = row.main_link()?;
+
+        // <a href="/company/co0071326/">Lucasfilm</a> [us]
+        let country = row
+            .loose_text()
+            .strip_prefix('[')
+            .and_then(|t| t.split_once(']'))
+            .map(|(country, _)| country.trim().to_string())
+            .filter(|country| !country.is_empty());
+
+        Ok(ByCompanyFoundItem {
+            name: a_tag.text.trim().to_string(),
+            link: a_tag.link,
+            country,
+        })
+    }
+}
+
+pub struct ByCompanyFoundItem {
+    name: String,
+    link: String,
+    country: Option<String>,
+}
+
+impl ByCompanyFoundItem {
+    pub fn get_company_id(&self) -> Option<String> {
+        link_id(&self.link)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn link(&self) -> &str {
+        self.link.as_ref()
+    }
+
+    /// Lowercase country code, like `us`.
+    pub fn country(&self) -> Option<&str> {
+        self.country.as_deref()
+    }
+}
+
+pub struct ByCompanyFound {
+    items: Vec<ByCompanyFoundItem>,
+    warnings: Vec<ParseError>,
+}
+
+impl ByCompanyFound {
+    pub fn items(&self) -> &[ByCompanyFoundItem] {
+        self.items.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByCompany {
+    type ParseResult = ByCompanyFound;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        find_uri(engine, "co", query)
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let rows = find_rows(&html).into_iter().map(ByCompany::parse_row);
+
+        let (items, warnings) = collect_items(self.lenient, rows)?;
+        Ok(ByCompanyFound { items, warnings })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::ByCompany;
+
+    #[test]
+    pub fn test_by_company_parser() {
+        let html = Html::parse_fragment(
+            r#"
+<div id="main">
+    <div class="article">
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="co"></a>Companies</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="result_text"> <a href="/company/co0071326/?ref_=fn_co_co_1">Lucasfilm</a> [us] </td>
+                    </tr>
+                    <tr class="findResult even">
+                        <td class="result_text"> <a href="/company/co0205561/?ref_=fn_co_co_2">Lucasfilm Animation</a> </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+    </div>
+</div>
+        "#,
+        );
+
+        let result = ByCompany::default().parse_result(html).unwrap();
+        assert_eq!(result.items().len(), 2);
+
+        let first = &result.items()[0];
+        assert_eq!(first.name(), "Lucasfilm");
+        assert_eq!(first.get_company_id().as_deref(), Some("co0071326"));
+        assert_eq!(first.country(), Some("us"));
+        assert_eq!(result.items()[1].country(), None);
+    }
+}
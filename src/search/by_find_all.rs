@@ -0,0 +1,217 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper,
+};
+use crate::ImdbSearchEngine;
+
+use super::by_character::{ByCharacter, ByCharacterFoundItem};
+use super::by_company::{ByCompany, ByCompanyFoundItem};
+use super::by_keyword::{ByKeyword, ByKeywordFoundItem};
+use super::by_name::{ByName, ByNameFoundItem};
+use super::by_title_find::{ByTitleFind, ByTitleFoundItem};
+use super::find::{find_sections, section_rows};
+use super::{parse_uri, By};
+
+/// Searches every category of the find page at once, `/find?q=`.
+pub struct ByFindAll {
+    lenient: bool,
+}
+
+// Title rows are read by `ByTitleFind::parse_row`, so this skips the
+// same rows `ByTitleFind` does by default.
+impl Default for ByFindAll {
+    fn default() -> Self {
+        Self { lenient: true }
+    }
+}
+
+impl ByFindAll {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// `None` for sections of a category this crate does not know.
+    fn parse_section(
+        lenient: bool,
+        section: ElementRef,
+    ) -> Option<Result<(FindSection, Vec<ParseError>), ParseError>> {
+        // <h3 class="findSectionHeader"><a name="tt"></a>Titles</h3>
+        let header_ele = section.select_first("h3.findSectionHeader")?;
+        let category = header_ele.select_first("a[name]")?.value().attr("name")?;
+        let parse_row: fn(ElementRef) -> Result<FindResult, ParseError> = match category {
+            "tt" => |tr| ByTitleFind::parse_row(tr).map(FindResult::Title),
+            "nm" => |tr| ByName::parse_row(tr).map(FindResult::Name),
+            "co" => |tr| ByCompany::parse_row(tr).map(FindResult::Company),
+            "kw" => |tr| ByKeyword::parse_row(tr).map(FindResult::Keyword),
+            "ch" => |tr| ByCharacter::parse_row(tr).map(FindResult::Character),
+            _ => return None,
+        };
+
+        let rows = section_rows(section).into_iter().map(parse_row);
+        Some(collect_items(lenient, rows).map(|(results, warnings)| {
+            let section = FindSection {
+                header: header_ele.text_content(),
+                results,
+            };
+            (section, warnings)
+        }))
+    }
+}
+
+/// One row of the find page, typed by the section it was found in.
+pub enum FindResult {
+    Title(ByTitleFoundItem),
+    Name(ByNameFoundItem),
+    Company(ByCompanyFoundItem),
+    Keyword(ByKeywordFoundItem),
+    Character(ByCharacterFoundItem),
+}
+
+pub struct FindSection {
+    header: String,
+    results: Vec<FindResult>,
+}
+
+impl FindSection {
+    /// As shown on the page, like `Titles`.
+    pub fn header(&self) -> &str {
+        self.header.as_ref()
+    }
+
+    pub fn results(&self) -> &[FindResult] {
+        self.results.as_ref()
+    }
+}
+
+pub struct FindAll {
+    sections: Vec<FindSection>,
+    warnings: Vec<ParseError>,
+}
+
+impl FindAll {
+    /// In the order of the page.
+    pub fn sections(&self) -> &[FindSection] {
+        self.sections.as_ref()
+    }
+
+    /// Every result of every section, in the order of the page.
+    pub fn results(&self) -> impl Iterator<Item = &FindResult> {
+        self.sections.iter().flat_map(|s| s.results.iter())
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByFindAll {
+    type ParseResult = FindAll;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(format!("{}/find?q={}", engine.base_uri(), query).as_str())
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let mut sections = vec![];
+        let mut warnings = vec![];
+        for section in find_sections(&html) {
+            if let Some(parsed) = ByFindAll::parse_section(self.lenient, section) {
+                let (section, section_warnings) = parsed?;
+                sections.push(section);
+                warnings.extend(section_warnings);
+            }
+        }
+
+        Ok(FindAll { sections, warnings })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::{ByFindAll, FindResult};
+
+    const FIND_ALL_PAGE: &str = r#"
+<div id="main">
+    <div class="article">
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="nm"></a>Names</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="primary_photo"> <a href="/name/nm0000434/"><img src="https://m.media-amazon.com/images/M/hamill.jpg"></a> </td>
+                        <td class="result_text"> <a href="/name/nm0000434/">Mark Hamill</a> <small>(Actor, <a href="/title/tt0076759/">Star Wars</a> (1977))</small> </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="tt"></a>Titles</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="primary_photo"> <a href="/title/tt0076759/"><img src="https://m.media-amazon.com/images/M/star_wars.jpg"></a> </td>
+                        <td class="result_text"> <a href="/title/tt0076759/">Star Wars</a> (1977) </td>
+                    </tr>
+                    <tr class="findResult even">
+                        <td class="primary_photo"> <a href="/title/tt0080684/"><img src="https://m.media-amazon.com/images/M/empire.jpg"></a> </td>
+                        <td class="result_text"> <a href="/title/tt0080684/">The Empire Strikes Back</a> (1980) </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="co"></a>Companies</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="result_text"> <a href="/company/co0071326/">Lucasfilm</a> [us] </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="kw"></a>Keywords</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="result_text"> <a href="/keyword/star-wars/">star-wars</a> (1,234 titles) </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+    </div>
+</div>
+"#;
+
+    #[test]
+    pub fn test_find_all_parser() {
+        let html = Html::parse_fragment(FIND_ALL_PAGE);
+
+        let result = ByFindAll::default().parse_result(html).unwrap();
+        let headers = result
+            .sections()
+            .iter()
+            .map(|s| s.header())
+            .collect::<Vec<&str>>();
+        assert_eq!(headers, ["Names", "Titles", "Companies", "Keywords"]);
+        assert_eq!(result.sections()[1].results().len(), 2);
+        assert_eq!(result.results().count(), 5);
+
+        match result.results().next() {
+            Some(FindResult::Name(name)) => assert_eq!(name.name(), "Mark Hamill"),
+            _ => panic!("the first result is not a name"),
+        }
+        match &result.sections()[3].results()[0] {
+            FindResult::Keyword(keyword) => assert_eq!(keyword.title_count(), Some(1234)),
+            _ => panic!("the keywords section holds other results"),
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper, link_id,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::imdb_id;
+use super::{parse_uri, By};
+
+/// Parenthesised notes that describe how someone was credited, not what they did.
+const ATTRIBUTE_PREFIXES: [&str; 8] = [
+    "uncredited",
+    "voice",
+    "archive footage",
+    "credit only",
+    "as ",
+    "scenes deleted",
+    "unconfirmed",
+    "also ",
+];
+
+/// Loads every credit of a title, `/title/{id}/fullcredits`, the query being
+/// the id like `tt0076759`.
+#[derive(Default)]
+pub struct ByFullCredits {
+    lenient: bool,
+}
+
+impl ByFullCredits {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    // <tr>
+    //     <td class="name"><a href="/name/nm0000184/"> George Lucas</a></td>
+    //     <td>...</td>
+    //     <td class="credit">(written by) (uncredited)</td>
+    // </tr>
+    fn parse_crew_row(tr: ElementRef) -> Result<CreditEntry, ParseError> {
+        let td = tr.require_first("td.name")?;
+        // People without an IMDb page are credited without a link.
+        let name = match td.select_first("a") {
+            Some(a) => ATag {
+                text: a.text_content(),
+                link: a.require_attr("href")?.to_string(),
+            },
+            None => ATag {
+                text: td.text_content(),
+                link: String::new(),
+            },
+        };
+        if name.text.is_empty() {
+            return Err(ParseError::new("td.name", &tr));
+        }
+        let credit = tr
+            .select_first("td.credit")
+            .map(|td| td.text_content())
+            .unwrap_or_default();
+
+        Ok(CreditEntry::new(name, &credit))
+    }
+
+    // <tr class="odd">
+    //     <td class="primary_photo">...</td>
+    //     <td><a href="/name/nm0000434/"> Mark Hamill</a></td>
+    //     <td class="ellipsis">...</td>
+    //     <td class="character"><a href="...">Luke Skywalker</a> (voice) <a>12 episodes, 2019-2020</a></td>
+    // </tr>
+    fn parse_cast_row(tr: ElementRef) -> Result<CreditEntry, ParseError> {
+        let name = tr.require_first("td:not(.primary_photo) > a")?;
+        let character = tr
+            .select_first("td.character")
+            .map(|td| td.text_content())
+            .unwrap_or_default();
+
+        Ok(CreditEntry::new(
+            ATag {
+                text: name.text_content(),
+                link: name.require_attr("href")?.to_string(),
+            },
+            &character,
+        ))
+    }
+
+    fn parse_department(
+        lenient: bool,
+        header: ElementRef,
+    ) -> Result<(Department, Vec<ParseError>), ParseError> {
+        let id = header
+            .value()
+            .attr("id")
+            .or_else(|| header.value().attr("name"))
+            .unwrap_or_default()
+            .to_string();
+        let table = header
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .next()
+            .filter(|e| e.value().name() == "table");
+
+        let mut results = vec![];
+        if let Some(table) = table {
+            for tr in table.select_all("tr") {
+                // Cast tables have label rows like "Rest of cast listed alphabetically:",
+                // crew tables spacer rows without a name cell.
+                if tr.select_first("td.castlist_label").is_some()
+                    || (id != "cast" && tr.select_first("td.name").is_none())
+                {
+                    continue;
+                }
+                results.push(if id == "cast" {
+                    ByFullCredits::parse_cast_row(tr)
+                } else {
+                    ByFullCredits::parse_crew_row(tr)
+                });
+            }
+        }
+
+        let (entries, warnings) = collect_items(lenient, results)?;
+        Ok((
+            Department {
+                id,
+                name: header.text_content(),
+                entries,
+            },
+            warnings,
+        ))
+    }
+}
+
+/// `12 episodes, 2019-2020` to `12`, along with the text before it.
+fn split_episodes(text: &str) -> (String, Option<u32>) {
+    let words = text.split_whitespace().collect::<Vec<&str>>();
+    if let Some(index) = words.iter().position(|w| w.starts_with("episode")) {
+        if let Some(count) = index
+            .checked_sub(1)
+            .and_then(|i| words[i].parse::<u32>().ok())
+        {
+            return (words[..index - 1].join(" "), Some(count));
+        }
+    }
+
+    (words.join(" "), None)
+}
+
+pub struct CreditEntry {
+    name: ATag,
+    role: Option<String>,
+    episodes: Option<u32>,
+    attributes: Vec<String>,
+}
+
+impl CreditEntry {
+    /// Splits the credit cell into the role, the episode count and the
+    /// parenthesised attributes.
+    fn new(name: ATag, credit: &str) -> Self {
+        let mut role = vec![];
+        let mut episodes = None;
+        let mut attributes = vec![];
+
+        let mut rest = credit;
+        while !rest.is_empty() {
+            let (outside, group) = match rest.split_once('(') {
+                Some((outside, after)) => match after.split_once(')') {
+                    Some((group, after)) => {
+                        rest = after;
+                        (outside, Some(group.trim()))
+                    }
+                    None => {
+                        rest = "";
+                        (outside, Some(after.trim()))
+                    }
+                },
+                None => (std::mem::take(&mut rest), None),
+            };
+
+            let (outside, count) = split_episodes(outside);
+            episodes = episodes.or(count);
+            if !outside.is_empty() {
+                role.push(outside);
+            }
+
+            if let Some(group) = group {
+                let (group, count) = split_episodes(group);
+                episodes = episodes.or(count);
+                if group.is_empty() {
+                    continue;
+                }
+                let lowercase = group.to_lowercase();
+                if ATTRIBUTE_PREFIXES.iter().any(|p| lowercase.starts_with(p)) {
+                    attributes.push(group);
+                } else if role.is_empty() {
+                    // Writers are credited as "(screenplay)", "(story)", ...
+                    role.push(group);
+                } else {
+                    attributes.push(group);
+                }
+            }
+        }
+
+        Self {
+            name,
+            role: Some(role.join(" ")).filter(|r| !r.is_empty()),
+            episodes,
+            attributes,
+        }
+    }
+
+    pub fn get_name_id(&self) -> Option<String> {
+        link_id(&self.name.link)
+    }
+
+    /// The link is empty for people without an IMDb page.
+    pub fn name(&self) -> &ATag {
+        &self.name
+    }
+
+    /// The character for the cast, the job for everyone else.
+    pub fn role(&self) -> Option<&str> {
+        self.role.as_deref()
+    }
+
+    /// Only set for series.
+    pub fn episodes(&self) -> Option<u32> {
+        self.episodes
+    }
+
+    /// Like `uncredited`, `voice` or `as Mark Hamill`.
+    pub fn attributes(&self) -> &[String] {
+        self.attributes.as_ref()
+    }
+}
+
+pub struct Department {
+    id: String,
+    name: String,
+    entries: Vec<CreditEntry>,
+}
+
+impl Department {
+    /// Like `director`, `writer` or `cast`.
+    pub fn id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    /// As shown on the page, like `Directed by`.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn entries(&self) -> &[CreditEntry] {
+        self.entries.as_ref()
+    }
+}
+
+pub struct FullCredits {
+    departments: Vec<Department>,
+    warnings: Vec<ParseError>,
+}
+
+impl FullCredits {
+    /// In the order of the page.
+    pub fn departments(&self) -> &[Department] {
+        self.departments.as_ref()
+    }
+
+    /// The entries of the department with `id`, empty when it is missing.
+    pub fn department(&self, id: &str) -> &[CreditEntry] {
+        self.departments
+            .iter()
+            .find(|d| d.id == id)
+            .map(|d| d.entries.as_ref())
+            .unwrap_or_default()
+    }
+
+    pub fn directors(&self) -> &[CreditEntry] {
+        self.department("director")
+    }
+
+    pub fn writers(&self) -> &[CreditEntry] {
+        self.department("writer")
+    }
+
+    pub fn cast(&self) -> &[CreditEntry] {
+        self.department("cast")
+    }
+
+    pub fn producers(&self) -> &[CreditEntry] {
+        self.department("producer")
+    }
+
+    pub fn music(&self) -> &[CreditEntry] {
+        self.department("composer")
+    }
+
+    pub fn cinematography(&self) -> &[CreditEntry] {
+        self.department("cinematographer")
+    }
+
+    pub fn editing(&self) -> &[CreditEntry] {
+        self.department("editor")
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByFullCredits {
+    type ParseResult = FullCredits;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/title/{}/fullcredits",
+                engine.base_uri(),
+                imdb_id("tt", query)?
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let mut departments = vec![];
+        let mut warnings = vec![];
+        for header in html.select_all("#fullcredits_content h4.dataHeaderWithBorder") {
+            let (department, department_warnings) =
+                ByFullCredits::parse_department(self.lenient, header)?;
+            departments.push(department);
+            warnings.extend(department_warnings);
+        }
+
+        Ok(FullCredits {
+            departments,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::ByFullCredits;
+
+    const FULL_CREDITS_PAGE: &str = r#"
+<div id="fullcredits_content" class="header">
+    <h4 name="director" id="director" class="dataHeaderWithBorder">Directed by&nbsp;</h4>
+    <table class="simpleTable simpleCreditsTable"><tbody>
+        <tr><td class="name"><a href="/name/nm0000184/?ref_=ttfc_fc_dr1"> George Lucas</a></td><td colspan="2"></td></tr>
+    </tbody></table>
+    <h4 name="writer" id="writer" class="dataHeaderWithBorder">Writing Credits</h4>
+    <table class="simpleTable simpleCreditsTable"><tbody>
+        <tr>
+            <td class="name"><a href="/name/nm0000184/?ref_=ttfc_fc_wr1"> George Lucas</a></td>
+            <td>...</td>
+            <td class="credit">(written by)</td>
+        </tr>
+        <tr>
+            <td class="name"><a href="/name/nm0001410/?ref_=ttfc_fc_wr2"> Gloria Katz</a></td>
+            <td>...</td>
+            <td class="credit">(revisions) (uncredited)</td>
+        </tr>
+        <tr><td colspan="3">&nbsp;</td></tr>
+        <tr>
+            <td class="name"> Alan Dean Foster </td>
+            <td>...</td>
+            <td class="credit">(novelization) (uncredited)</td>
+        </tr>
+    </tbody></table>
+    <h4 name="cast" id="cast" class="dataHeaderWithBorder">Cast <span>(in credits order)</span></h4>
+    <table class="cast_list">
+        <tr><td colspan="4" class="castlist_label"></td></tr>
+        <tr class="odd">
+            <td class="primary_photo"><a href="/name/nm0000434/"><img src="https://m.media-amazon.com/images/M/hamill.jpg"></a></td>
+            <td><a href="/name/nm0000434/?ref_=ttfc_fc_cl_t1"> Mark Hamill</a></td>
+            <td class="ellipsis">...</td>
+            <td class="character"><a href="/title/tt0076759/characters/nm0000434">Luke Skywalker</a></td>
+        </tr>
+        <tr><td colspan="4" class="castlist_label">Rest of cast listed alphabetically:</td></tr>
+        <tr class="even">
+            <td class="primary_photo"><a href="/name/nm0001190/"><img src="https://m.media-amazon.com/images/M/edwards.jpg"></a></td>
+            <td><a href="/name/nm0001190/?ref_=ttfc_fc_cl_t2"> James Earl Jones</a></td>
+            <td class="ellipsis">...</td>
+            <td class="character">
+                <a href="/title/tt0076759/characters/nm0001190">Darth Vader</a> (voice) (uncredited)
+                <a href="/title/tt0076759/episodes" class="toggle-episodes">3 episodes, 2019-2020</a>
+            </td>
+        </tr>
+    </table>
+    <h4 name="producer" id="producer" class="dataHeaderWithBorder">Produced by&nbsp;</h4>
+    <table class="simpleTable simpleCreditsTable"><tbody>
+        <tr>
+            <td class="name"><a href="/name/nm0464935/?ref_=ttfc_fc_pr1"> Gary Kurtz</a></td>
+            <td>...</td>
+            <td class="credit">producer</td>
+        </tr>
+    </tbody></table>
+</div>
+"#;
+
+    #[test]
+    fn test_full_credits_parser() {
+        let result = ByFullCredits::default()
+            .parse_result(Html::parse_document(FULL_CREDITS_PAGE))
+            .unwrap();
+
+        let ids = result
+            .departments()
+            .iter()
+            .map(|d| d.id())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, ["director", "writer", "cast", "producer"]);
+        assert_eq!(result.departments()[0].name(), "Directed by");
+
+        let director = &result.directors()[0];
+        assert_eq!(director.name().text(), "George Lucas");
+        assert_eq!(director.get_name_id().as_deref(), Some("nm0000184"));
+        assert_eq!(director.role(), None);
+
+        assert_eq!(result.writers()[0].role(), Some("written by"));
+        assert_eq!(result.writers()[1].role(), Some("revisions"));
+        assert_eq!(result.writers()[1].attributes(), ["uncredited"]);
+        // Spacer rows are skipped, names without a page kept.
+        assert_eq!(result.writers().len(), 3);
+        assert_eq!(result.writers()[2].name().text(), "Alan Dean Foster");
+        assert_eq!(result.writers()[2].get_name_id(), None);
+
+        assert_eq!(result.cast().len(), 2);
+        assert_eq!(result.cast()[0].role(), Some("Luke Skywalker"));
+        let vader = &result.cast()[1];
+        assert_eq!(vader.role(), Some("Darth Vader"));
+        assert_eq!(vader.attributes(), ["voice", "uncredited"]);
+        assert_eq!(vader.episodes(), Some(3));
+
+        assert_eq!(result.producers()[0].role(), Some("producer"));
+        assert!(result.editing().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_crew_rows_are_warnings() {
+        let page = FULL_CREDITS_PAGE.replace(
+            r#"<a href="/name/nm0464935/?ref_=ttfc_fc_pr1"> Gary Kurtz</a>"#,
+            "",
+        );
+
+        assert!(ByFullCredits::default()
+            .parse_result(Html::parse_document(&page))
+            .is_err());
+        let result = ByFullCredits::default()
+            .lenient(true)
+            .parse_result(Html::parse_document(&page))
+            .unwrap();
+        assert!(result.producers().is_empty());
+        assert_eq!(result.warnings().len(), 1);
+        assert_eq!(result.writers().len(), 3);
+    }
+}
@@ -0,0 +1,144 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{collect_items, link_id};
+use crate::ImdbSearchEngine;
+
+use super::find::{find_rows, find_uri, FindRow};
+use super::By;
+
+/// Finds plot keywords, `/find?s=kw`.
+pub struct ByKeyword {
+    lenient: bool,
+}
+
+impl Default for ByKeyword {
+    fn default() -> Self {
+        Self { lenient: true }
+    }
+}
+
+impl ByKeyword {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub(crate) fn parse_row(tr: ElementRef) -> Result<ByKeywordFoundItem, ParseError> {
+        let row = FindRow::parse(tr)?;
+        let a_tag = row.main_link()?;
+
+        // <a href="/keyword/star-wars/">star-wars</a> (1,234 titles)
+        let title_count = row
+            .loose_text()
+            .strip_prefix('(')
+            .and_then(|t| t.split_whitespace().next().map(|n| n.replace(',', "")))
+            .and_then(|n| n.parse().ok());
+
+        Ok(ByKeywordFoundItem {
+            keyword: a_tag.text.trim().to_string(),
+            link: a_tag.link,
+            title_count,
+        })
+    }
+}
+
+pub struct ByKeywordFoundItem {
+    keyword: String,
+    link: String,
+    title_count: Option<u32>,
+}
+
+impl ByKeywordFoundItem {
+    /// The keyword as it appears in urls, like `star-wars`.
+    pub fn get_slug(&self) -> Option<String> {
+        link_id(&self.link)
+    }
+
+    pub fn keyword(&self) -> &str {
+        self.keyword.as_ref()
+    }
+
+    pub fn link(&self) -> &str {
+        self.link.as_ref()
+    }
+
+    /// Titles tagged with the keyword.
+    pub fn title_count(&self) -> Option<u32> {
+        self.title_count
+    }
+}
+
+pub struct ByKeywordFound {
+    items: Vec<ByKeywordFoundItem>,
+    warnings: Vec<ParseError>,
+}
+
+impl ByKeywordFound {
+    pub fn items(&self) -> &[ByKeywordFoundItem] {
+        self.items.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByKeyword {
+    type ParseResult = ByKeywordFound;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        find_uri(engine, "kw", query)
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let rows = find_rows(&html).into_iter().map(ByKeyword::parse_row);
+
+        let (items, warnings) = collect_items(self.lenient, rows)?;
+        Ok(ByKeywordFound { items, warnings })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::ByKeyword;
+
+    #[test]
+    pub fn test_by_keyword_parser() {
+        let html = Html::parse_fragment(
+            r#"
+<div id="main">
+    <div class="article">
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="kw"></a>Keywords</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="result_text"> <a href="/keyword/star-wars/?ref_=fn_kw_kw_1">star-wars</a> (1,234 titles) </td>
+                    </tr>
+                    <tr class="findResult even">
+                        <td class="result_text"> <a href="/keyword/star-wars-parody/?ref_=fn_kw_kw_2">star-wars-parody</a> (17 titles) </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+    </div>
+</div>
+        "#,
+        );
+
+        let result = ByKeyword::default().parse_result(html).unwrap();
+        assert_eq!(result.items().len(), 2);
+
+        let first = &result.items()[0];
+        assert_eq!(first.keyword(), "star-wars");
+        assert_eq!(first.get_slug().as_deref(), Some("star-wars"));
+        assert_eq!(first.title_count(), Some(1234));
+        assert_eq!(result.items()[1].title_count(), Some(17));
+    }
+}
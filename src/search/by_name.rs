@@ -0,0 +1,172 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper, link_id,
+};
+
+use super::find::{find_rows, find_uri, FindRow};
+use super::By;
+
+/// Finds people by name, `/find?s=nm`.
+pub struct ByName {
+    lenient: bool,
+}
+
+impl Default for ByName {
+    fn default() -> Self {
+        Self { lenient: true }
+    }
+}
+
+impl ByName {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub(crate) fn parse_row(tr: ElementRef) -> Result<ByNameFoundItem, ParseError> {
+        let row = FindRow::parse(tr)?;
+        let a_tag = row.main_link()?;
+
+        // <small>(Actor, <a href="/title/tt0076759/">Star Wars</a> (1977))</small>
+        let known_for = row
+            .text
+            .select_first("small")
+            .map(|small| {
+                let text = small.text_content();
+                text.strip_prefix('(')
+                    .and_then(|t| t.strip_suffix(')'))
+                    .unwrap_or(&text)
+                    .to_string()
+            })
+            .filter(|text| !text.is_empty());
+
+        Ok(ByNameFoundItem {
+            name: a_tag.text,
+            link: a_tag.link,
+            img_uri: row.image_src()?,
+            known_for,
+        })
+    }
+}
+
+pub struct ByNameFoundItem {
+    name: String,
+    link: String,
+    img_uri: String,
+    known_for: Option<String>,
+}
+
+impl ByNameFoundItem {
+    pub fn get_name_id(&self) -> Option<String> {
+        link_id(&self.link)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn link(&self) -> &str {
+        self.link.as_ref()
+    }
+
+    pub fn img_uri(&self) -> &str {
+        self.img_uri.as_ref()
+    }
+
+    /// Main profession and best known title, like `Actor, Star Wars (1977)`.
+    pub fn known_for(&self) -> Option<&str> {
+        self.known_for.as_deref()
+    }
+}
+
+pub struct ByNameFound {
+    items: Vec<ByNameFoundItem>,
+    warnings: Vec<ParseError>,
+}
+
+impl ByNameFound {
+    pub fn items(&self) -> &[ByNameFoundItem] {
+        self.items.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByName {
+    type ParseResult = ByNameFound;
+
+    fn get_uri(
+        &self,
+        engine: &crate::prelude::ImdbSearchEngine,
+        query: &str,
+    ) -> Result<Uri, ImdbError> {
+        find_uri(engine, "nm", query)
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let rows = find_rows(&html).into_iter().map(ByName::parse_row);
+
+        let (items, warnings) = collect_items(self.lenient, rows)?;
+        Ok(ByNameFound { items, warnings })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::ByName;
+
+    #[test]
+    pub fn test_by_name_parser() {
+        let html = Html::parse_fragment(
+            r#"
+<div id="main">
+    <div class="article">
+        <h1 class="findHeader">Displaying 3 results for <span class="findSearchTerm">"Hamill"</span></h1>
+        <div class="findSection">
+            <h3 class="findSectionHeader"><a name="nm"></a>Names</h3>
+            <table class="findList">
+                <tbody>
+                    <tr class="findResult odd">
+                        <td class="primary_photo"> <a href="/name/nm0000434/?ref_=fn_nm_nm_1"><img
+                                    src="https://m.media-amazon.com/images/M/MV5BOGY4NWNlM2QtMzVjYy00OGY1LWI4N2UtZDNlYWE2ZThjYmRmXkEyXkFqcGdeQXVyMTQxNzMzNDI@._V1_UY44_CR1,0,32,44_AL_.jpg"></a>
+                        </td>
+                        <td class="result_text"> <a href="/name/nm0000434/?ref_=fn_nm_nm_1">Mark Hamill</a>
+                            <small>(Actor, <a href="/title/tt0076759/?ref_=fn_nm_nm_1a">Star Wars: Episode IV - A
+                                    New Hope</a> (1977))</small> </td>
+                    </tr>
+                    <tr class="findResult even">
+                        <td class="primary_photo"> <a href="/name/nm0357071/?ref_=fn_nm_nm_2"><img
+                                    src="https://m.media-amazon.com/images/S/sash/9FayPGLPcrscMjU.png"></a>
+                        </td>
+                        <td class="result_text"> <a href="/name/nm0357071/?ref_=fn_nm_nm_2">Nathan Hamill</a> </td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+    </div>
+</div>
+        "#,
+        );
+
+        let result = ByName::default().parse_result(html).unwrap();
+        assert_eq!(result.items().len(), 2);
+
+        let first = &result.items()[0];
+        assert_eq!(first.name(), "Mark Hamill");
+        assert_eq!(first.get_name_id().as_deref(), Some("nm0000434"));
+        assert_eq!(
+            first.known_for(),
+            Some("Actor, Star Wars: Episode IV - A New Hope (1977)")
+        );
+        assert_eq!(result.items()[1].known_for(), None);
+    }
+}
@@ -0,0 +1,488 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+use serde_json::Value;
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::json::{json_str, ld_json};
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, get_selector,
+    html_parser::HtmlParserHelper, link_id,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::{imdb_id, Date};
+use super::{parse_uri, By};
+
+/// Loads a person page, `/name/{id}/`, the query being the id like `nm0000434`.
+///
+/// Fields come from the JSON-LD block first, then from the rendered page.
+///
+/// Unlike [`ByTitleId`](super::by_title_id::ByTitleId), the markup read here
+/// is the classic one (`#filmography`, `#knownfor`, `#name-born-info`). The
+/// redesigned name page only renders a few credits per category and loads
+/// the rest on demand, so it has no complete filmography to read. Served
+/// that layout, parsing fails on the missing `#filmography`, or when lenient
+/// keeps the JSON-LD fields and reports it in the warnings.
+#[derive(Default)]
+pub struct ByNameId {
+    lenient: bool,
+}
+
+impl ByNameId {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    // <div class="knownfor-title">
+    //     <div class="knownfor-title-role">
+    //         <a href="/title/tt0076759/" class="knownfor-ellipsis">Star Wars</a>
+    //         <span class="knownfor-ellipsis">Luke Skywalker</span>
+    //     </div>
+    //     <div class="knownfor-year"><span class="knownfor-ellipsis">(1977)</span></div>
+    // </div>
+    fn parse_known_for(element: ElementRef) -> Option<FilmographyEntry> {
+        let title = element.select_first("div.knownfor-title-role a")?;
+
+        Some(FilmographyEntry {
+            title: ATag {
+                text: title.text_content(),
+                link: title.value().attr("href")?.to_string(),
+            },
+            year: element
+                .select_first("div.knownfor-year")
+                .and_then(|y| parse_year(&y.text_content())),
+            character: element
+                .select_first("div.knownfor-title-role span")
+                .map(|r| r.text_content())
+                .filter(|r| !r.is_empty()),
+            kind: None,
+        })
+    }
+
+    // <div class="filmo-row odd" id="actor-tt0076759">
+    //     <span class="year_column">&nbsp;1977</span>
+    //     <b><a href="/title/tt0076759/">Star Wars</a></b> (TV Series)
+    //     <br/>
+    //     Luke Skywalker
+    // </div>
+    fn parse_filmography_row(row: ElementRef) -> Result<FilmographyEntry, ParseError> {
+        let title = row.require_first("b > a")?;
+
+        let mut before_br = vec![];
+        let mut after_br = vec![];
+        let mut seen_br = false;
+        for child in row.children() {
+            let text = match ElementRef::wrap(child) {
+                Some(element) => match element.value().name() {
+                    "br" => {
+                        seen_br = true;
+                        continue;
+                    }
+                    // The year, the title and the episode lists.
+                    "span" | "b" | "div" => continue,
+                    _ => element.text_content(),
+                },
+                None => match child.value().as_text() {
+                    Some(text) => text.split_whitespace().collect::<Vec<&str>>().join(" "),
+                    None => continue,
+                },
+            };
+            if seen_br {
+                after_br.push(text);
+            } else {
+                before_br.push(text);
+            }
+        }
+        let join =
+            |texts: Vec<String>| Some(texts.join(" ").trim().to_string()).filter(|t| !t.is_empty());
+
+        Ok(FilmographyEntry {
+            title: ATag {
+                text: title.text_content(),
+                link: title.require_attr("href")?.to_string(),
+            },
+            year: row
+                .select_first("span.year_column")
+                .and_then(|y| parse_year(&y.text_content())),
+            character: join(after_br),
+            kind: join(before_br)
+                .map(|k| k.trim_start_matches('(').trim_end_matches(')').to_string()),
+        })
+    }
+
+    fn parse_filmography(
+        lenient: bool,
+        html: &Html,
+    ) -> Result<(Vec<FilmographyCategory>, Vec<ParseError>), ParseError> {
+        // The redesigned page has no such list, a person without credits has an empty one.
+        if let Err(missing) = html.require_first("#filmography") {
+            return if lenient {
+                Ok((vec![], vec![missing]))
+            } else {
+                Err(missing)
+            };
+        }
+
+        let mut categories = vec![];
+        let mut warnings = vec![];
+        // Every div.head is followed by the div.filmo-category-section holding its rows.
+        for head in html.select_all("#filmography > div.head") {
+            let section = head
+                .next_siblings()
+                .filter_map(ElementRef::wrap)
+                .next()
+                .filter(|e| e.value().classes().any(|c| c == "filmo-category-section"));
+            let row_selector = get_selector("div.filmo-row");
+            let rows = section
+                .map(|s| s.select(&row_selector).collect::<Vec<ElementRef>>())
+                .unwrap_or_default()
+                .into_iter()
+                .map(ByNameId::parse_filmography_row);
+            let (entries, category_warnings) = collect_items(lenient, rows)?;
+
+            categories.push(FilmographyCategory {
+                id: head
+                    .value()
+                    .attr("data-category")
+                    .unwrap_or_default()
+                    .to_string(),
+                name: head
+                    .select_first("a[name]")
+                    .map(|a| a.text_content())
+                    .unwrap_or_default(),
+                entries,
+            });
+            warnings.extend(category_warnings);
+        }
+
+        Ok((categories, warnings))
+    }
+}
+
+/// The first four digit year in `&nbsp;2019/I`, `(1977)` or `2019-2020`.
+fn parse_year(text: &str) -> Option<u16> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|part| part.len() == 4)?
+        .parse()
+        .ok()
+}
+
+pub struct FilmographyEntry {
+    title: ATag,
+    year: Option<u16>,
+    character: Option<String>,
+    kind: Option<String>,
+}
+
+impl FilmographyEntry {
+    pub fn get_title_id(&self) -> Option<String> {
+        link_id(&self.title.link)
+    }
+
+    pub fn title(&self) -> &ATag {
+        &self.title
+    }
+
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    /// Who they played, or what they did for crew categories.
+    pub fn character(&self) -> Option<&str> {
+        self.character.as_deref()
+    }
+
+    /// Like `TV Series` or `Video Game`, `None` for movies.
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+}
+
+pub struct FilmographyCategory {
+    id: String,
+    name: String,
+    entries: Vec<FilmographyEntry>,
+}
+
+impl FilmographyCategory {
+    /// Like `actor`, `director` or `producer`.
+    pub fn id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    /// As shown on the page, like `Actor`.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Newest first, as listed by IMDb.
+    pub fn entries(&self) -> &[FilmographyEntry] {
+        self.entries.as_ref()
+    }
+}
+
+pub struct PersonDetails {
+    name: String,
+    birth_date: Option<Date>,
+    birth_place: Option<String>,
+    death_date: Option<Date>,
+    death_place: Option<String>,
+    photo: Option<String>,
+    height: Option<String>,
+    bio: Option<String>,
+    known_for: Vec<FilmographyEntry>,
+    filmography: Vec<FilmographyCategory>,
+    warnings: Vec<ParseError>,
+}
+
+impl PersonDetails {
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn birth_date(&self) -> Option<Date> {
+        self.birth_date
+    }
+
+    pub fn birth_place(&self) -> Option<&str> {
+        self.birth_place.as_deref()
+    }
+
+    pub fn death_date(&self) -> Option<Date> {
+        self.death_date
+    }
+
+    pub fn death_place(&self) -> Option<&str> {
+        self.death_place.as_deref()
+    }
+
+    pub fn photo(&self) -> Option<&str> {
+        self.photo.as_deref()
+    }
+
+    /// As written by IMDb, like `5′ 9″ (1.75 m)`.
+    pub fn height(&self) -> Option<&str> {
+        self.height.as_deref()
+    }
+
+    /// The short bio of the page, the full one lives on `/bio`.
+    pub fn bio(&self) -> Option<&str> {
+        self.bio.as_deref()
+    }
+
+    pub fn known_for(&self) -> &[FilmographyEntry] {
+        self.known_for.as_ref()
+    }
+
+    /// In the order of the page.
+    pub fn filmography(&self) -> &[FilmographyCategory] {
+        self.filmography.as_ref()
+    }
+
+    /// The entries of the category with `id`, empty when it is missing.
+    pub fn filmography_in(&self, id: &str) -> &[FilmographyEntry] {
+        self.filmography
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.entries.as_ref())
+            .unwrap_or_default()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for ByNameId {
+    type ParseResult = PersonDetails;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(format!("{}/name/{}/", engine.base_uri(), imdb_id("nm", query)?).as_str())
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let ld = ld_json(&html).unwrap_or(Value::Null);
+        let ld_field = |key: &str| ld.get(key).and_then(json_str);
+        let text = |selector: &str| {
+            html.select_first(selector)
+                .map(|e| e.text_content())
+                .filter(|t| !t.is_empty())
+        };
+        let date = |selector: &str| {
+            html.select_first(selector)
+                .and_then(|t| Date::parse(t.value().attr("datetime")?))
+        };
+
+        let name = match ld_field("name") {
+            Some(name) => name,
+            None => match html.select_first(r#"h1[data-testid="hero__pageTitle"]"#) {
+                Some(h1) => h1.text_content(),
+                None => html.require_first("h1.header span")?.text_content(),
+            },
+        };
+        let (filmography, warnings) = ByNameId::parse_filmography(self.lenient, &html)?;
+
+        Ok(PersonDetails {
+            name,
+            birth_date: ld_field("birthDate")
+                .and_then(|d| Date::parse(&d))
+                .or_else(|| date("#name-born-info time")),
+            birth_place: text(r#"#name-born-info a[href*="birth_place"]"#),
+            death_date: ld_field("deathDate")
+                .and_then(|d| Date::parse(&d))
+                .or_else(|| date("#name-death-info time")),
+            death_place: text(r#"#name-death-info a[href*="death_place"]"#),
+            photo: ld_field("image").or_else(|| {
+                html.select_first("#name-poster")
+                    .and_then(|img| Some(img.value().attr("src")?.to_string()))
+            }),
+            height: text("#details-height")
+                .map(|h| h.trim_start_matches("Height:").trim().to_string()),
+            bio: ld_field("description").or_else(|| text("div.name-trivia-bio-text div.inline")),
+            known_for: html
+                .select_all("#knownfor div.knownfor-title")
+                .into_iter()
+                .filter_map(ByNameId::parse_known_for)
+                .collect(),
+            filmography,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::filters::Date;
+    use crate::search::By;
+
+    use super::ByNameId;
+
+    const NAME_PAGE: &str = r#"
+<html>
+<head>
+    <script type="application/ld+json">{"@context":"https://schema.org","@type":"Person",
+        "url":"/name/nm0000434/","name":"Mark Hamill","image":"https://m.media-amazon.com/images/M/hamill.jpg",
+        "birthDate":"1951-09-25"}</script>
+</head>
+<body>
+    <h1 class="header"><span class="itemprop">Mark Hamill</span></h1>
+    <div id="name-born-info" class="txt-block">
+        Born: <time datetime="1951-9-25">September 25, 1951</time>
+        in <a href="/search/name?birth_place=Oakland,%20California,%20USA">Oakland, California, USA</a>
+    </div>
+    <div id="details-height" class="txt-block"><h4 class="inline">Height:</h4> 5′ 9″ (1.75 m)</div>
+    <div class="name-trivia-bio-text"><div class="inline">Mark Hamill is best known for playing Luke Skywalker.</div></div>
+    <div id="knownfor">
+        <div class="knownfor-title">
+            <div class="knownfor-title-role">
+                <a href="/title/tt0076759/" class="knownfor-ellipsis">Star Wars</a>
+                <span class="knownfor-ellipsis">Luke Skywalker</span>
+            </div>
+            <div class="knownfor-year"><span class="knownfor-ellipsis">(1977)</span></div>
+        </div>
+    </div>
+    <div id="filmography">
+        <div id="filmo-head-actor" class="head" data-category="actor">
+            <a name="actor">Actor</a> (2 credits)
+        </div>
+        <div class="filmo-category-section">
+            <div class="filmo-row odd" id="actor-tt0944947">
+                <span class="year_column">&nbsp;2019-2020</span>
+                <b><a href="/title/tt0944947/">What We Do in the Shadows</a></b>
+                (TV Series)
+                <br/>
+                <a href="/title/tt0944947/characters/nm0000434">Jim the Vampire</a>
+                <div class="filmo-episodes">- Episode 1</div>
+            </div>
+            <div class="filmo-row even" id="actor-tt0076759">
+                <span class="year_column">&nbsp;1977</span>
+                <b><a href="/title/tt0076759/">Star Wars</a></b>
+                <br/>
+                Luke Skywalker
+            </div>
+        </div>
+        <div id="filmo-head-producer" class="head" data-category="producer">
+            <a name="producer">Producer</a> (1 credit)
+        </div>
+        <div class="filmo-category-section">
+            <div class="filmo-row odd" id="producer-tt0318850">
+                <span class="year_column">&nbsp;2004</span>
+                <b><a href="/title/tt0318850/">Comic Book: The Movie</a></b>
+                (Video)
+                <br/>
+                producer
+            </div>
+        </div>
+    </div>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn test_person_details_parser() {
+        let person = ByNameId::default()
+            .parse_result(Html::parse_document(NAME_PAGE))
+            .unwrap();
+
+        assert_eq!(person.name(), "Mark Hamill");
+        assert_eq!(person.birth_date(), Some(Date::new(1951, 9, 25).unwrap()));
+        assert_eq!(person.birth_place(), Some("Oakland, California, USA"));
+        assert_eq!(person.death_date(), None);
+        assert_eq!(
+            person.photo(),
+            Some("https://m.media-amazon.com/images/M/hamill.jpg")
+        );
+        assert_eq!(person.height(), Some("5′ 9″ (1.75 m)"));
+        assert_eq!(
+            person.bio(),
+            Some("Mark Hamill is best known for playing Luke Skywalker.")
+        );
+
+        let known_for = &person.known_for()[0];
+        assert_eq!(known_for.get_title_id().as_deref(), Some("tt0076759"));
+        assert_eq!(known_for.year(), Some(1977));
+        assert_eq!(known_for.character(), Some("Luke Skywalker"));
+
+        let categories = person
+            .filmography()
+            .iter()
+            .map(|c| (c.id(), c.name(), c.entries().len()))
+            .collect::<Vec<(&str, &str, usize)>>();
+        assert_eq!(
+            categories,
+            [("actor", "Actor", 2), ("producer", "Producer", 1)]
+        );
+
+        let series = &person.filmography_in("actor")[0];
+        assert_eq!(series.get_title_id().as_deref(), Some("tt0944947"));
+        assert_eq!(series.year(), Some(2019));
+        assert_eq!(series.character(), Some("Jim the Vampire"));
+        assert_eq!(series.kind(), Some("TV Series"));
+        let movie = &person.filmography_in("actor")[1];
+        assert_eq!(movie.character(), Some("Luke Skywalker"));
+        assert_eq!(movie.kind(), None);
+        assert_eq!(person.filmography_in("producer")[0].kind(), Some("Video"));
+    }
+
+    #[test]
+    fn test_redesigned_page_reports_missing_filmography() {
+        let page = r#"<h1 data-testid="hero__pageTitle"><span>Mark Hamill</span></h1>"#;
+        let error = ByNameId::default()
+            .parse_result(Html::parse_document(page))
+            .err()
+            .unwrap();
+        assert_eq!(error.selector(), "#filmography");
+
+        let details = ByNameId::default()
+            .lenient(true)
+            .parse_result(Html::parse_document(page))
+            .unwrap();
+        assert_eq!(details.name(), "Mark Hamill");
+        assert!(details.filmography().is_empty());
+        assert_eq!(details.warnings()[0].selector(), "#filmography");
+    }
+}
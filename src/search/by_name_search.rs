@@ -0,0 +1,413 @@
+use std::ops::{RangeBounds, RangeInclusive};
+
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper, link_id,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::{Date, Gender, NameFilters, Span, StarSign};
+use super::pagination::PageInfo;
+use super::{parse_uri, By, Paginated};
+
+pub struct NameSearchItem {
+    name: String,
+    link: String,
+    photo: Option<String>,
+    professions: Vec<String>,
+    known_for: Option<ATag>,
+    bio: String,
+}
+
+impl NameSearchItem {
+    pub fn get_name_id(&self) -> Option<String> {
+        link_id(&self.link)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn link(&self) -> &str {
+        self.link.as_ref()
+    }
+
+    /// `None` when IMDb only shows its placeholder.
+    pub fn photo(&self) -> Option<&str> {
+        self.photo.as_deref()
+    }
+
+    /// Like `["Actor", "Producer"]`, in the order IMDb lists them.
+    pub fn professions(&self) -> &[String] {
+        self.professions.as_ref()
+    }
+
+    pub fn known_for(&self) -> Option<&ATag> {
+        self.known_for.as_ref()
+    }
+
+    /// The start of the biography, possibly cut off by IMDb.
+    pub fn bio(&self) -> &str {
+        self.bio.as_ref()
+    }
+}
+
+pub struct NameSearch {
+    items: Vec<NameSearchItem>,
+    warnings: Vec<ParseError>,
+    page: PageInfo,
+    query: ByNameSearch,
+}
+
+impl NameSearch {
+    pub fn items(&self) -> &[NameSearchItem] {
+        self.items.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+
+    /// All matches across every page, from the "1-50 of N names." header.
+    pub fn total_results(&self) -> Option<u32> {
+        self.page.total_results
+    }
+
+    /// Positions of this page's items among all matches, starting at `1`.
+    pub fn range(&self) -> Option<RangeInclusive<u32>> {
+        self.page.range.clone()
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.page.next_start.is_some()
+    }
+
+    /// The `ByNameSearch` for the following page, keeping every other option.
+    pub fn next_query(&self) -> Option<ByNameSearch> {
+        Some(ByNameSearch {
+            start: self.page.next_start?,
+            ..self.query.clone()
+        })
+    }
+}
+
+/// Advanced people search, `/search/name/`, the query being the name.
+#[derive(Debug, Clone)]
+pub struct ByNameSearch {
+    pub(crate) start: u16,
+    pub(crate) count: u8,
+    pub(crate) lenient: bool,
+    pub(crate) filters: NameFilters,
+}
+
+impl ByNameSearch {
+    pub fn new(start: u16, count: u8) -> Self {
+        Self {
+            start,
+            count,
+            lenient: false,
+            filters: NameFilters::default(),
+        }
+    }
+
+    /// Starts a search with advanced filters, from the first page of 50 items.
+    pub fn builder() -> ByNameSearchBuilder {
+        ByNameSearchBuilder::default()
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    fn parse_item(element: ElementRef, contents: ElementRef) -> Result<NameSearchItem, ParseError> {
+        let name_ele = contents.require_first("h3.lister-item-header>a")?;
+        let link = name_ele.require_attr("href")?.to_string();
+
+        let photo = match element.select_first("div.lister-item-image img") {
+            Some(img) => Some(img.require_attr("src")?.to_string()),
+            None => None,
+        }
+        .filter(|src| !src.contains("/sash/"));
+
+        // <p class="text-muted">Actor, Producer <span class="ghost">|</span> <a href="/title/...">Star Wars</a></p>
+        let (professions, known_for) = match contents.select_first("p.text-muted") {
+            Some(p) => {
+                let professions = p
+                    .text()
+                    .take_while(|t| t.trim() != "|")
+                    .collect::<String>()
+                    .split(',')
+                    .map(|t| t.split_whitespace().collect::<Vec<&str>>().join(" "))
+                    .filter(|t| !t.is_empty())
+                    .collect();
+                let known_for = p.select_first("a").and_then(|a| a.parse_a_tag());
+                (professions, known_for)
+            }
+            None => (vec![], None),
+        };
+
+        let bio = contents
+            .select_all("p:not(.text-muted)")
+            .first()
+            .map(|p| p.text_content())
+            .unwrap_or_default();
+
+        Ok(NameSearchItem {
+            name: name_ele.text_content(),
+            link,
+            photo,
+            professions,
+            known_for: known_for.map(|a| ATag {
+                text: a.text.trim().to_string(),
+                link: a.link,
+            }),
+            bio,
+        })
+    }
+}
+
+impl Default for ByNameSearch {
+    fn default() -> Self {
+        Self::new(1, 50)
+    }
+}
+
+/// Builds a [`ByNameSearch`] with the advanced search filters of `/search/name/`.
+///
+/// Any range works, open ended ones like `Date::year(1950)..` included.
+/// Invalid values are reported by [`ByNameSearchBuilder::build`].
+#[derive(Debug, Default)]
+pub struct ByNameSearchBuilder {
+    by: ByNameSearch,
+    error: Option<ImdbError>,
+}
+
+impl ByNameSearchBuilder {
+    fn check(mut self, result: Result<(), ImdbError>) -> Self {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+        self
+    }
+
+    pub fn start(mut self, start: u16) -> Self {
+        self.by.start = start;
+        self
+    }
+
+    pub fn count(mut self, count: u8) -> Self {
+        self.by.count = count;
+        self
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.by.lenient = lenient;
+        self
+    }
+
+    pub fn birth_date(mut self, range: impl RangeBounds<Date>) -> Self {
+        let span = Span::new("birth_date", range);
+        let result = span.map(|span| self.by.filters.birth_date = Some(span));
+        self.check(result)
+    }
+
+    pub fn death_date(mut self, range: impl RangeBounds<Date>) -> Self {
+        let span = Span::new("death_date", range);
+        let result = span.map(|span| self.by.filters.death_date = Some(span));
+        self.check(result)
+    }
+
+    /// Free text, like `Oakland, California, USA`.
+    pub fn birth_place(mut self, place: &str) -> Self {
+        let place = place.trim();
+        if place.is_empty() {
+            return self.check(Err(ImdbError::InvalidQuery(
+                "birth_place is empty".to_string(),
+            )));
+        }
+        self.by.filters.birth_place = Some(place.to_string());
+        self
+    }
+
+    pub fn genders(mut self, genders: impl IntoIterator<Item = Gender>) -> Self {
+        self.by.filters.genders.extend(genders);
+        self
+    }
+
+    pub fn star_sign(mut self, star_sign: StarSign) -> Self {
+        self.by.filters.star_sign = Some(star_sign);
+        self
+    }
+
+    /// Title ids, like `tt0076759`, the people must be known for.
+    pub fn known_for<'a>(mut self, title_ids: impl IntoIterator<Item = &'a str>) -> Self {
+        let result = self.by.filters.add_known_for(title_ids);
+        self.check(result)
+    }
+
+    /// The first invalid value given to the builder, if any, as an [`ImdbError::InvalidQuery`].
+    pub fn build(self) -> Result<ByNameSearch, ImdbError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.by),
+        }
+    }
+}
+
+impl By for ByNameSearch {
+    type ParseResult = NameSearch;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/search/name/?name={}{}&start={}&count={}",
+                engine.base_uri(),
+                query,
+                self.filters.to_query_string(),
+                self.start,
+                self.count
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let mut results = vec![];
+        for element in html.select_all("div.lister-list>div.lister-item") {
+            if let Some(contents) = element.select_first("div.lister-item-content") {
+                results.push(ByNameSearch::parse_item(element, contents));
+            }
+        }
+
+        let (items, warnings) = collect_items(self.lenient, results)?;
+        Ok(NameSearch {
+            items,
+            warnings,
+            page: PageInfo::parse(&html, self.start),
+            query: self.clone(),
+        })
+    }
+}
+
+impl Paginated for ByNameSearch {
+    type Item = NameSearchItem;
+
+    fn next_page(&self, result: &NameSearch) -> Option<Self> {
+        result.next_query()
+    }
+
+    fn into_items(result: NameSearch) -> Vec<NameSearchItem> {
+        result.items
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::prelude::ImdbSearchEngine;
+    use crate::search::filters::{Date, Gender, StarSign};
+    use crate::search::By;
+
+    use super::ByNameSearch;
+
+    const NAME_SEARCH_PAGE: &str = r#"
+<div class="desc">
+    <span>1-2 of 1,204 names.</span>
+    <a href="/search/name/?name=hamill&start=3&ref_=rlm" class="lister-page-next next-page">Next »</a>
+</div>
+<div class="lister-list">
+    <div class="lister-item mode-detail">
+        <div class="lister-item-image">
+            <a href="/name/nm0000434"><img alt="Mark Hamill" src="https://m.media-amazon.com/images/M/hamill.jpg"></a>
+        </div>
+        <div class="lister-item-content">
+            <h3 class="lister-item-header">
+                <span class="lister-item-index unbold text-primary">1.</span>
+                <a href="/name/nm0000434"> Mark Hamill
+</a>
+            </h3>
+            <p class="text-muted text-small">
+                Actor, Producer
+                <span class="ghost">|</span>
+                <a href="/title/tt0076759/"> Star Wars</a>
+            </p>
+            <p>Mark Hamill is best known for playing Luke Skywalker in the original
+                Star Wars trilogy...</p>
+        </div>
+    </div>
+    <div class="lister-item mode-detail">
+        <div class="lister-item-image">
+            <a href="/name/nm0357071"><img alt="Nathan Hamill" src="https://m.media-amazon.com/images/S/sash/no_photo.png"></a>
+        </div>
+        <div class="lister-item-content">
+            <h3 class="lister-item-header"><a href="/name/nm0357071"> Nathan Hamill</a></h3>
+        </div>
+    </div>
+</div>
+"#;
+
+    #[test]
+    fn test_name_search_parser() {
+        let result = ByNameSearch::new(1, 2)
+            .parse_result(Html::parse_document(NAME_SEARCH_PAGE))
+            .unwrap();
+        assert_eq!(result.items().len(), 2);
+        assert_eq!(result.total_results(), Some(1204));
+        assert_eq!(result.range(), Some(1..=2));
+        assert_eq!(result.next_query().unwrap().start, 3);
+
+        let first = &result.items()[0];
+        assert_eq!(first.name(), "Mark Hamill");
+        assert_eq!(first.get_name_id().as_deref(), Some("nm0000434"));
+        assert_eq!(
+            first.photo(),
+            Some("https://m.media-amazon.com/images/M/hamill.jpg")
+        );
+        assert_eq!(first.professions(), ["Actor", "Producer"]);
+        assert_eq!(first.known_for().unwrap().text(), "Star Wars");
+        assert_eq!(first.known_for().unwrap().link(), "/title/tt0076759/");
+        assert!(first.bio().starts_with("Mark Hamill is best known"));
+
+        let second = &result.items()[1];
+        assert_eq!(second.photo(), None);
+        assert!(second.professions().is_empty());
+        assert!(second.known_for().is_none());
+        assert_eq!(second.bio(), "");
+    }
+
+    #[test]
+    fn test_name_filters_in_uri() {
+        let by = ByNameSearch::builder()
+            .birth_date(Date::year(1950)..=Date::new(1960, 6, 30).unwrap())
+            .death_date(..=Date::year(2020))
+            .birth_place("Oakland, California")
+            .genders([Gender::Male, Gender::NonBinary])
+            .star_sign(StarSign::Libra)
+            .known_for(["tt0076759"])
+            .build()
+            .unwrap();
+
+        let uri = by
+            .get_uri(&ImdbSearchEngine::new(), "mark%20hamill")
+            .unwrap();
+        assert_eq!(
+            uri.to_string(),
+            "https://www.imdb.com/search/name/?name=mark%20hamill\
+             &birth_date=1950,1960-06-30&death_date=,2020\
+             &birth_place=Oakland%2C%20California&gender=male,non_binary\
+             &star_sign=libra&roles=tt0076759&start=1&count=50"
+        );
+
+        assert!(ByNameSearch::builder()
+            .known_for(["nm0000434"])
+            .build()
+            .is_err());
+        assert!(ByNameSearch::builder().birth_place(" ").build().is_err());
+    }
+}
@@ -0,0 +1,404 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html, Node};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{
+    element_parser::ElementParserHelper, html_parser::HtmlParserHelper, parenthesised,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::imdb_id;
+use super::{parse_uri, By};
+
+/// Loads the parents guide of a title, `/title/{id}/parentalguide`, the query
+/// being the id like `tt0076759`.
+#[derive(Debug, Clone, Default)]
+pub struct ByParentsGuide;
+
+impl ByParentsGuide {
+    // <li class="ipl-inline-list__item">
+    //     <a href="/search/title?certificates=US:PG">United States:PG</a> (certificate #24870)
+    // </li>
+    fn parse_certification(li: ElementRef) -> Option<Certification> {
+        let (country, rating) = li
+            .select_first("a")?
+            .text_content()
+            .split_once(':')
+            .map(|(country, rating)| (country.trim().to_string(), rating.trim().to_string()))?;
+
+        Some(Certification {
+            country,
+            rating,
+            notes: parenthesised(&li.text_content()),
+        })
+    }
+
+    // <section id="advisory-violence">
+    //     <span class="ipl-status-pill ipl-status-pill--moderate">Moderate</span>
+    //     <a class="advisory-severity-vote__message" href="/title/tt0076759/parentalguide">281 of 502 found this moderate</a>
+    //     <ul class="ipl-zebra-list">
+    //         <li class="ipl-zebra-list__item">Several stormtroopers are shot. <div class="ipl-hideable-container">...</div></li>
+    //     </ul>
+    // </section>
+    fn parse_advisory(html: &Html, category: AdvisoryCategory) -> Option<Advisory> {
+        let section = html.select_first(&format!("section#advisory-{}", category.section_id()))?;
+        let spoilers = html.select_first(&format!(
+            "section#advisory-spoilers section#advisory-spoiler-{}",
+            category.section_id()
+        ));
+        let section_items = |section: ElementRef, spoiler: bool| {
+            section
+                .select_all("li.ipl-zebra-list__item")
+                .into_iter()
+                .map(move |li| AdvisoryItem {
+                    text: Self::item_text(li),
+                    spoiler,
+                })
+                .filter(|item| !item.text.is_empty())
+                .collect::<Vec<AdvisoryItem>>()
+        };
+
+        // "281 of 502 found this moderate"
+        let votes = section
+            .select_first("a.advisory-severity-vote__message")
+            .and_then(|message| {
+                let message = message.text_content().replace(',', "");
+                let mut words = message.split_whitespace();
+                let agreeing = words.next()?.parse().ok()?;
+                let total = words.nth(1)?.parse().ok()?;
+                Some((agreeing, total))
+            });
+
+        let mut items = section_items(section, false);
+        if let Some(spoilers) = spoilers {
+            items.extend(section_items(spoilers, true));
+        }
+
+        Some(Advisory {
+            category,
+            severity: section
+                .select_first("span.ipl-status-pill")
+                .and_then(|pill| Severity::parse(&pill.text_content())),
+            votes,
+            items,
+        })
+    }
+
+    /// Only the text of the item, not its edit and vote links.
+    fn item_text(li: ElementRef) -> String {
+        let mut text = String::new();
+        for child in li.children() {
+            match child.value() {
+                Node::Text(t) => text.push_str(t),
+                Node::Element(e)
+                    if e.classes().any(|c| {
+                        c == "ipl-hideable-container" || c == "zebra-list__item-footer"
+                    }) => {}
+                Node::Element(_) => {
+                    if let Some(element) = ElementRef::wrap(child) {
+                        text.extend(element.text());
+                    }
+                }
+                _ => {}
+            }
+        }
+        text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+}
+
+/// How bad a category is, as voted by users. Ordered from `None` to `Severe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    None,
+    Mild,
+    Moderate,
+    Severe,
+}
+
+impl Severity {
+    fn parse(pill: &str) -> Option<Self> {
+        match pill.to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "mild" => Some(Self::Mild),
+            "moderate" => Some(Self::Moderate),
+            "severe" => Some(Self::Severe),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdvisoryCategory {
+    SexAndNudity,
+    ViolenceAndGore,
+    Profanity,
+    AlcoholDrugsAndSmoking,
+    FrighteningScenes,
+}
+
+impl AdvisoryCategory {
+    /// In the order of the page.
+    pub const ALL: [Self; 5] = [
+        Self::SexAndNudity,
+        Self::ViolenceAndGore,
+        Self::Profanity,
+        Self::AlcoholDrugsAndSmoking,
+        Self::FrighteningScenes,
+    ];
+
+    fn section_id(&self) -> &'static str {
+        match self {
+            Self::SexAndNudity => "nudity",
+            Self::ViolenceAndGore => "violence",
+            Self::Profanity => "profanity",
+            Self::AlcoholDrugsAndSmoking => "alcohol",
+            Self::FrighteningScenes => "frightening",
+        }
+    }
+}
+
+pub struct AdvisoryItem {
+    text: String,
+    spoiler: bool,
+}
+
+impl AdvisoryItem {
+    pub fn text(&self) -> &str {
+        self.text.as_ref()
+    }
+
+    /// Items from the spoilers part of the page.
+    pub fn spoiler(&self) -> bool {
+        self.spoiler
+    }
+}
+
+pub struct Advisory {
+    category: AdvisoryCategory,
+    severity: Option<Severity>,
+    votes: Option<(u32, u32)>,
+    items: Vec<AdvisoryItem>,
+}
+
+impl Advisory {
+    pub fn category(&self) -> AdvisoryCategory {
+        self.category
+    }
+
+    /// `None` while nobody voted.
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// Users who voted for the shown severity.
+    pub fn severity_votes(&self) -> Option<u32> {
+        self.votes.map(|(agreeing, _)| agreeing)
+    }
+
+    pub fn total_votes(&self) -> Option<u32> {
+        self.votes.map(|(_, total)| total)
+    }
+
+    /// Spoiler items come last.
+    pub fn items(&self) -> &[AdvisoryItem] {
+        self.items.as_ref()
+    }
+}
+
+pub struct Certification {
+    country: String,
+    rating: String,
+    notes: Vec<String>,
+}
+
+impl Certification {
+    /// As shown on the page, like `United States`.
+    pub fn country(&self) -> &str {
+        self.country.as_ref()
+    }
+
+    /// Like `PG` or `12`.
+    pub fn rating(&self) -> &str {
+        self.rating.as_ref()
+    }
+
+    /// Like `certificate #24870` or `re-rating`.
+    pub fn notes(&self) -> &[String] {
+        self.notes.as_ref()
+    }
+}
+
+pub struct ParentsGuide {
+    mpaa: Option<String>,
+    certifications: Vec<Certification>,
+    advisories: Vec<Advisory>,
+}
+
+impl ParentsGuide {
+    /// The MPAA reason, like `Rated PG for sci-fi violence`.
+    pub fn mpaa(&self) -> Option<&str> {
+        self.mpaa.as_deref()
+    }
+
+    pub fn certifications(&self) -> &[Certification] {
+        self.certifications.as_ref()
+    }
+
+    /// Every certification given in `country`, as named on the page.
+    pub fn certifications_in<'a>(
+        &'a self,
+        country: &'a str,
+    ) -> impl Iterator<Item = &'a Certification> {
+        self.certifications
+            .iter()
+            .filter(move |c| c.country == country)
+    }
+
+    pub fn advisories(&self) -> &[Advisory] {
+        self.advisories.as_ref()
+    }
+
+    pub fn advisory(&self, category: AdvisoryCategory) -> Option<&Advisory> {
+        self.advisories.iter().find(|a| a.category == category)
+    }
+}
+
+impl By for ByParentsGuide {
+    type ParseResult = ParentsGuide;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/title/{}/parentalguide",
+                engine.base_uri(),
+                imdb_id("tt", query)?
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        html.require_first(r#"section[id^="advisory-"]"#)?;
+
+        Ok(ParentsGuide {
+            mpaa: html
+                .select_first("section#certificates tr#mpaa-rating td:last-child")
+                .map(|td| td.text_content())
+                .filter(|mpaa| !mpaa.is_empty()),
+            certifications: html
+                .select_all("section#certificates li.ipl-inline-list__item")
+                .into_iter()
+                .filter_map(ByParentsGuide::parse_certification)
+                .collect(),
+            advisories: AdvisoryCategory::ALL
+                .iter()
+                .filter_map(|category| ByParentsGuide::parse_advisory(&html, *category))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::{AdvisoryCategory, ByParentsGuide, Severity};
+
+    const PARENTS_GUIDE_PAGE: &str = r#"
+<section id="certificates" class="article listo">
+    <table class="ipl-zebra-list">
+        <tr class="ipl-zebra-list__item" id="mpaa-rating">
+            <td class="ipl-zebra-list__label">MPAA</td>
+            <td>Rated PG for sci-fi violence and brief mild language</td>
+        </tr>
+        <tr class="ipl-zebra-list__item" id="certifications-list">
+            <td class="ipl-zebra-list__label">Certification</td>
+            <td>
+                <ul class="ipl-inline-list">
+                    <li class="ipl-inline-list__item"><a href="/search/title?certificates=AR:Atp">Argentina:Atp</a></li>
+                    <li class="ipl-inline-list__item"><a href="/search/title?certificates=US:PG">United States:PG</a> (certificate #24870)</li>
+                    <li class="ipl-inline-list__item"><a href="/search/title?certificates=US:G">United States:G</a> (original rating)</li>
+                </ul>
+            </td>
+        </tr>
+    </table>
+</section>
+<section id="advisory-nudity" class="article listo">
+    <h4 class="ipl-list-title__content">Sex &amp; Nudity</h4>
+    <span class="ipl-status-pill ipl-status-pill--none">None</span>
+    <a class="advisory-severity-vote__message" href="/title/tt0076759/parentalguide">1,023 of 1,100 found this none</a>
+    <ul class="ipl-zebra-list">
+        <li class="ipl-zebra-list__item">Leia's dress is a bit sheer.
+            <div class="zebra-list__item-footer ipl-hideable-container"><a href="/title/tt0076759/parentalguide/edit">Edit</a></div>
+        </li>
+    </ul>
+</section>
+<section id="advisory-violence" class="article listo">
+    <span class="ipl-status-pill ipl-status-pill--moderate">Moderate</span>
+    <a class="advisory-severity-vote__message" href="/title/tt0076759/parentalguide">281 of 502 found this moderate</a>
+    <ul class="ipl-zebra-list">
+        <li class="ipl-zebra-list__item">Several stormtroopers are shot with blasters.</li>
+        <li class="ipl-zebra-list__item">Han shoots <i>Greedo</i> in a cantina.
+            <div class="ipl-hideable-container"><a href="/title/tt0076759/parentalguide/edit">Edit</a></div>
+        </li>
+    </ul>
+</section>
+<section id="advisory-profanity" class="article listo">
+    <span class="ipl-status-pill ipl-status-pill--mild">Mild</span>
+    <ul class="ipl-zebra-list"></ul>
+</section>
+<section id="advisory-alcohol" class="article listo">
+    <span class="ipl-status-pill">No Rating</span>
+</section>
+<section id="advisory-spoilers" class="article listo">
+    <section id="advisory-spoiler-violence">
+        <ul class="ipl-zebra-list">
+            <li class="ipl-zebra-list__item">A main character is struck down by a lightsaber.</li>
+        </ul>
+    </section>
+</section>
+"#;
+
+    #[test]
+    fn test_parents_guide_parser() {
+        let guide = ByParentsGuide
+            .parse_result(Html::parse_document(PARENTS_GUIDE_PAGE))
+            .unwrap();
+
+        assert!(guide.mpaa().unwrap().starts_with("Rated PG"));
+        assert_eq!(guide.certifications().len(), 3);
+        let us = guide.certifications_in("United States").collect::<Vec<_>>();
+        assert_eq!(us.len(), 2);
+        assert_eq!(us[0].rating(), "PG");
+        assert_eq!(us[0].notes(), ["certificate #24870"]);
+
+        assert_eq!(guide.advisories().len(), 4);
+        let nudity = guide.advisory(AdvisoryCategory::SexAndNudity).unwrap();
+        assert_eq!(nudity.severity(), Some(Severity::None));
+        assert_eq!(nudity.severity_votes(), Some(1023));
+        assert_eq!(nudity.total_votes(), Some(1100));
+        assert_eq!(nudity.items()[0].text(), "Leia's dress is a bit sheer.");
+
+        let violence = guide.advisory(AdvisoryCategory::ViolenceAndGore).unwrap();
+        assert_eq!(violence.severity(), Some(Severity::Moderate));
+        assert!(violence.severity() > Some(Severity::Mild));
+        assert_eq!(violence.items().len(), 3);
+        assert_eq!(
+            violence.items()[1].text(),
+            "Han shoots Greedo in a cantina."
+        );
+        assert!(!violence.items()[1].spoiler());
+        assert!(violence.items()[2].spoiler());
+
+        let alcohol = guide
+            .advisory(AdvisoryCategory::AlcoholDrugsAndSmoking)
+            .unwrap();
+        assert_eq!(alcohol.severity(), None);
+        assert!(alcohol.items().is_empty());
+        assert!(guide
+            .advisory(AdvisoryCategory::FrighteningScenes)
+            .is_none());
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{
+    element_parser::ElementParserHelper, get_selector, html_parser::HtmlParserHelper,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::{imdb_id, Gender};
+use super::{parse_uri, By};
+
+/// Loads the ratings breakdown of a title, `/title/{id}/ratings`, the query
+/// being the id like `tt0076759`.
+#[derive(Debug, Clone, Default)]
+pub struct ByRatings;
+
+/// "1,234" and "8.6" alike, `None` for the "-" of empty cells.
+fn number<T: FromStr>(text: &str) -> Option<T> {
+    text.trim().replace(',', "").parse().ok()
+}
+
+impl ByRatings {
+    // 1,004,373
+    // IMDb users have given a <a href="...">weighted average</a> vote of 8.6 / 10
+    fn parse_summary(summary: &str) -> (Option<u32>, Option<f32>) {
+        let words = summary.split_whitespace().collect::<Vec<&str>>();
+        let votes = words.first().and_then(|w| number(w));
+        let average = words
+            .iter()
+            .position(|w| *w == "of")
+            .and_then(|of| number(words.get(of + 1)?));
+        (votes, average)
+    }
+
+    // <td class="ratingTable">
+    //     <div class="bigcell">8.6</div>
+    //     <div class="smallcell"><a href="/title/tt0076759/ratings?demo=imdb_users">1,004,373</a></div>
+    // </td>
+    fn parse_cell(td: ElementRef) -> Option<DemographicRating> {
+        Some(DemographicRating {
+            rating: number(&td.select_first("div.bigcell")?.text_content())?,
+            votes: number(&td.select_first("div.smallcell")?.text_content())?,
+        })
+    }
+}
+
+/// The age columns of the demographic breakdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AgeBracket {
+    All,
+    Under18,
+    Aged18To29,
+    Aged30To44,
+    Aged45Plus,
+}
+
+impl AgeBracket {
+    fn parse(header: &str) -> Option<Self> {
+        match header {
+            "All Ages" => Some(Self::All),
+            "<18" => Some(Self::Under18),
+            "18-29" => Some(Self::Aged18To29),
+            "30-44" => Some(Self::Aged30To44),
+            "45+" => Some(Self::Aged45Plus),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DemographicRating {
+    rating: f32,
+    votes: u32,
+}
+
+impl DemographicRating {
+    pub fn rating(&self) -> f32 {
+        self.rating
+    }
+
+    pub fn votes(&self) -> u32 {
+        self.votes
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Ratings {
+    weighted_average: Option<f32>,
+    votes: u32,
+    histogram: [u32; 10],
+    by_age: HashMap<(Option<Gender>, AgeBracket), DemographicRating>,
+    top_1000: Option<DemographicRating>,
+    us: Option<DemographicRating>,
+    non_us: Option<DemographicRating>,
+}
+
+impl Ratings {
+    /// `None` for titles nobody rated yet.
+    pub fn weighted_average(&self) -> Option<f32> {
+        self.weighted_average
+    }
+
+    pub fn votes(&self) -> u32 {
+        self.votes
+    }
+
+    /// Votes per star, from 1 star at index 0 to 10 stars at index 9.
+    pub fn histogram(&self) -> &[u32; 10] {
+        &self.histogram
+    }
+
+    /// Votes given `stars` out of 10.
+    pub fn votes_for(&self, stars: u8) -> Option<u32> {
+        self.histogram
+            .get(usize::from(stars).checked_sub(1)?)
+            .copied()
+    }
+
+    /// `None` as gender for every voter, `None` back when IMDb has no votes
+    /// for that group.
+    pub fn demographic(
+        &self,
+        gender: Option<Gender>,
+        age: AgeBracket,
+    ) -> Option<DemographicRating> {
+        self.by_age.get(&(gender, age)).copied()
+    }
+
+    pub fn top_1000(&self) -> Option<DemographicRating> {
+        self.top_1000
+    }
+
+    pub fn us(&self) -> Option<DemographicRating> {
+        self.us
+    }
+
+    pub fn non_us(&self) -> Option<DemographicRating> {
+        self.non_us
+    }
+}
+
+impl By for ByRatings {
+    type ParseResult = Ratings;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/title/{}/ratings",
+                engine.base_uri(),
+                imdb_id("tt", query)?
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let page = html.require_first("div.title-ratings-sub-page")?;
+        let mut ratings = Ratings::default();
+
+        if let Some(summary) = page.select_first("div.allText > div.allText") {
+            let (votes, average) = ByRatings::parse_summary(&summary.text_content());
+            ratings.votes = votes.unwrap_or_default();
+            ratings.weighted_average = average;
+        }
+
+        // The histogram, the age and gender breakdown and the top 1000 and
+        // US breakdown, told apart by their headers.
+        let th = get_selector("th");
+        let td = get_selector("td");
+        for table in page.select_all("table") {
+            let headers = table
+                .select(&th)
+                .map(|h| h.text_content())
+                .collect::<Vec<String>>();
+            for row in table.select_all("tr") {
+                let cells = row.select(&td).collect::<Vec<ElementRef>>();
+                if headers.is_empty() {
+                    // <td><div class="rightAligned">10</div></td>
+                    // <td><div class="topAligned"><div class="leftAligned">446,567</div></div></td>
+                    let stars = row
+                        .select_first("div.rightAligned")
+                        .and_then(|s| number::<usize>(&s.text_content()))
+                        .filter(|s| (1..=10).contains(s));
+                    let votes = row
+                        .select_first("div.leftAligned")
+                        .and_then(|v| number(&v.text_content()));
+                    if let (Some(stars), Some(votes)) = (stars, votes) {
+                        ratings.histogram[stars - 1] = votes;
+                    }
+                } else if headers.iter().any(|h| h == "All Ages") {
+                    let gender = match cells.first().map(|c| c.text_content()).as_deref() {
+                        Some("All") => None,
+                        Some("Males") => Some(Gender::Male),
+                        Some("Females") => Some(Gender::Female),
+                        _ => continue,
+                    };
+                    for (header, cell) in headers.iter().zip(cells).skip(1) {
+                        let age = AgeBracket::parse(header);
+                        if let (Some(age), Some(rating)) = (age, ByRatings::parse_cell(cell)) {
+                            ratings.by_age.insert((gender, age), rating);
+                        }
+                    }
+                } else {
+                    for (header, cell) in headers.iter().zip(cells) {
+                        let rating = ByRatings::parse_cell(cell);
+                        match header.as_str() {
+                            "Top 1000 Voters" => ratings.top_1000 = rating,
+                            "US Users" => ratings.us = rating,
+                            "Non-US Users" => ratings.non_us = rating,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(ratings)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::filters::Gender;
+    use crate::search::By;
+
+    use super::{AgeBracket, ByRatings};
+
+    const RATINGS_PAGE: &str = r#"
+<div class="title-ratings-sub-page">
+    <div class="allText">
+        <div class="allText">
+            1,004,373
+            IMDb users have given a <a href="/help/show_leaf?votestopfaq">weighted average</a> vote of 8.6 / 10
+        </div>
+        <br/>
+        <table cellpadding="0" cellspacing="0">
+            <tr>
+                <td align="right"><div class="rightAligned">10</div></td>
+                <td><div class="topAligned"><div class="bigcell">44.5%</div></div></td>
+                <td align="right"><div class="leftAligned">446,567</div></td>
+            </tr>
+            <tr>
+                <td align="right"><div class="rightAligned">9</div></td>
+                <td><div class="topAligned"><div class="bigcell">24.1%</div></div></td>
+                <td align="right"><div class="leftAligned">242,052</div></td>
+            </tr>
+            <tr>
+                <td align="right"><div class="rightAligned">1</div></td>
+                <td><div class="topAligned"><div class="bigcell">0.9%</div></div></td>
+                <td align="right"><div class="leftAligned">9,012</div></td>
+            </tr>
+        </table>
+        <div class="sectionHeading">Rating By Demographic</div>
+        <table cellpadding="0" cellspacing="0">
+            <tr>
+                <th>&nbsp;</th>
+                <th><div class="tableHeadings">All Ages</div></th>
+                <th><div class="tableHeadings">&lt;18</div></th>
+                <th><div class="tableHeadings">18-29</div></th>
+                <th><div class="tableHeadings">30-44</div></th>
+                <th><div class="tableHeadings">45+</div></th>
+            </tr>
+            <tr>
+                <td align="left"><div class="allText"><div class="leftAligned">All</div></div></td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.6</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=imdb_users">1,004,373</a></div>
+                </td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.9</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=aged_under_18">1,234</a></div>
+                </td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.5</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=aged_18_29">123,456</a></div>
+                </td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.5</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=aged_30_44">345,678</a></div>
+                </td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.4</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=aged_45_plus">98,765</a></div>
+                </td>
+            </tr>
+            <tr>
+                <td align="left"><div class="allText"><div class="leftAligned">Females</div></div></td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.3</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=females">87,654</a></div>
+                </td>
+                <td align="center" class="ratingTable">-</td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.2</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=females_aged_18_29">12,345</a></div>
+                </td>
+                <td align="center" class="ratingTable">-</td>
+                <td align="center" class="ratingTable">-</td>
+            </tr>
+        </table>
+        <table cellpadding="0" cellspacing="0">
+            <tr>
+                <th><div class="tableHeadings">Top 1000 Voters</div></th>
+                <th><div class="tableHeadings">US Users</div></th>
+                <th><div class="tableHeadings">Non-US Users</div></th>
+            </tr>
+            <tr>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">7.9</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=top_1000_voters">681</a></div>
+                </td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.8</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=us_users">156,789</a></div>
+                </td>
+                <td align="center" class="ratingTable">
+                    <div class="bigcell">8.5</div>
+                    <div class="smallcell"><a href="/title/tt0076759/ratings?demo=non_us_users">456,789</a></div>
+                </td>
+            </tr>
+        </table>
+    </div>
+</div>
+"#;
+
+    #[test]
+    fn test_ratings_parser() {
+        let ratings = ByRatings
+            .parse_result(Html::parse_document(RATINGS_PAGE))
+            .unwrap();
+
+        assert_eq!(ratings.weighted_average(), Some(8.6));
+        assert_eq!(ratings.votes(), 1004373);
+        assert_eq!(ratings.votes_for(10), Some(446567));
+        assert_eq!(ratings.votes_for(1), Some(9012));
+        assert_eq!(ratings.votes_for(5), Some(0));
+        assert_eq!(ratings.votes_for(0), None);
+
+        let all = ratings.demographic(None, AgeBracket::All).unwrap();
+        assert_eq!((all.rating(), all.votes()), (8.6, 1004373));
+        let young = ratings
+            .demographic(Some(Gender::Female), AgeBracket::Aged18To29)
+            .unwrap();
+        assert_eq!((young.rating(), young.votes()), (8.2, 12345));
+        assert_eq!(
+            ratings.demographic(Some(Gender::Female), AgeBracket::Under18),
+            None
+        );
+        assert_eq!(
+            ratings.demographic(Some(Gender::Male), AgeBracket::All),
+            None
+        );
+
+        assert_eq!(ratings.top_1000().unwrap().votes(), 681);
+        assert_eq!(ratings.us().unwrap().rating(), 8.8);
+        assert_eq!(ratings.non_us().unwrap().votes(), 456789);
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::countries::country_code_of;
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper,
+    parenthesised,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::{imdb_id, Date};
+use super::{parse_uri, By};
+
+/// Loads the release dates and alternate titles of a title,
+/// `/title/{id}/releaseinfo`, the query being the id like `tt0076759`.
+#[derive(Debug, Clone, Default)]
+pub struct ByReleaseInfo {
+    lenient: bool,
+}
+
+impl ByReleaseInfo {
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    // <tr class="ipl-zebra-list__item release-date-item">
+    //     <td class="release-date-item__country-name"><a href="/calendar/?region=us&ref_=ttrel_rel_1">United States</a></td>
+    //     <td class="release-date-item__date" align="right">25 May 1977</td>
+    //     <td class="release-date-item__attributes">(Oakland, California) (premiere)</td>
+    // </tr>
+    fn parse_release(tr: ElementRef) -> Result<Release, ParseError> {
+        let country = tr.require_first("td.release-date-item__country-name a")?;
+        let href = country.require_attr("href")?;
+
+        Ok(Release {
+            country: country.text_content(),
+            country_code: href
+                .split_once("region=")
+                .and_then(|(_, region)| region.split('&').next())
+                .filter(|region| !region.is_empty())
+                .map(|region| region.to_lowercase()),
+            date: tr
+                .select_first("td.release-date-item__date")
+                .and_then(|d| Date::parse_long(&d.text_content())),
+            attributes: tr
+                .select_first("td.release-date-item__attributes")
+                .map(|a| parenthesised(&a.text_content()))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| ReleaseAttribute::parse(&a))
+                .collect(),
+        })
+    }
+
+    // <tr class="ipl-zebra-list__item aka-item">
+    //     <td class="aka-item__name">Canada (French title)</td>
+    //     <td class="aka-item__title">La guerre des étoiles</td>
+    // </tr>
+    fn parse_aka(tr: ElementRef) -> Result<Aka, ParseError> {
+        let name = tr.require_first("td.aka-item__name")?.text_content();
+        let country = match name.split_once('(') {
+            Some((country, _)) => country.trim(),
+            None => name.as_str(),
+        };
+
+        Ok(Aka {
+            title: tr.require_first("td.aka-item__title")?.text_content(),
+            // The original title is the only one without a country.
+            country: Some(country.to_string()).filter(|c| !c.is_empty()),
+            country_code: None,
+            notes: parenthesised(&name),
+        })
+    }
+}
+
+/// What the parentheses after a release date say about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseAttribute {
+    Premiere,
+    Limited,
+    /// The festival, like `Cannes Film Festival`.
+    Festival(String),
+    /// Anything else, like the city of a premiere or `re-release`.
+    Other(String),
+}
+
+impl ReleaseAttribute {
+    fn parse(attribute: &str) -> Self {
+        match attribute {
+            "premiere" => Self::Premiere,
+            "limited" => Self::Limited,
+            festival if festival.contains("Festival") => Self::Festival(festival.to_string()),
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+pub struct Release {
+    country: String,
+    country_code: Option<String>,
+    date: Option<Date>,
+    attributes: Vec<ReleaseAttribute>,
+}
+
+impl Release {
+    /// As shown on the page, like `United States`.
+    pub fn country(&self) -> &str {
+        self.country.as_ref()
+    }
+
+    /// Lowercase ISO 3166 code, like `us`.
+    pub fn country_code(&self) -> Option<&str> {
+        self.country_code.as_deref()
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    pub fn attributes(&self) -> &[ReleaseAttribute] {
+        self.attributes.as_ref()
+    }
+
+    pub fn is_premiere(&self) -> bool {
+        self.attributes.contains(&ReleaseAttribute::Premiere)
+    }
+
+    pub fn is_limited(&self) -> bool {
+        self.attributes.contains(&ReleaseAttribute::Limited)
+    }
+
+    pub fn festival(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|a| match a {
+            ReleaseAttribute::Festival(festival) => Some(festival.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// An alternate title, "also known as".
+pub struct Aka {
+    title: String,
+    country: Option<String>,
+    country_code: Option<String>,
+    notes: Vec<String>,
+}
+
+impl Aka {
+    pub fn title(&self) -> &str {
+        self.title.as_ref()
+    }
+
+    /// `None` for the original title.
+    pub fn country(&self) -> Option<&str> {
+        self.country.as_deref()
+    }
+
+    /// The AKAs table only names countries, their code is taken from the
+    /// release dates of the same country, else looked up by name.
+    pub fn country_code(&self) -> Option<&str> {
+        self.country_code.as_deref()
+    }
+
+    /// Like `original title`, `French title` or `working title`.
+    pub fn notes(&self) -> &[String] {
+        self.notes.as_ref()
+    }
+
+    /// From notes like `French title` or `literal English title`.
+    pub fn language(&self) -> Option<&str> {
+        self.notes.iter().find_map(|note| {
+            let language = note.strip_suffix(" title")?.split_whitespace().last()?;
+            Some(language).filter(|l| l.starts_with(char::is_uppercase))
+        })
+    }
+
+    /// Working, alternative and similar titles, not the one the title was
+    /// released under.
+    fn is_secondary(&self) -> bool {
+        self.notes.iter().any(|note| {
+            [
+                "alternative",
+                "working",
+                "informal",
+                "short",
+                "video box",
+                "review",
+            ]
+            .iter()
+            .any(|kind| note.contains(kind))
+        })
+    }
+}
+
+pub struct ReleaseInfo {
+    releases: Vec<Release>,
+    akas: Vec<Aka>,
+    warnings: Vec<ParseError>,
+}
+
+impl ReleaseInfo {
+    /// In the order of the page, which is by date.
+    pub fn releases(&self) -> &[Release] {
+        self.releases.as_ref()
+    }
+
+    pub fn akas(&self) -> &[Aka] {
+        self.akas.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+
+    pub fn original_title(&self) -> Option<&str> {
+        self.akas
+            .iter()
+            .find(|aka| aka.notes.iter().any(|n| n == "original title"))
+            .map(|aka| aka.title())
+    }
+
+    /// The title as released in `country`, an ISO 3166 code like `fr`.
+    ///
+    /// Working and alternative titles are only used when the country has no
+    /// other title. `None` when the title is known under the same name there.
+    pub fn localised_title(&self, country: &str) -> Option<&str> {
+        let country = country.trim().to_lowercase();
+        let akas = self
+            .akas
+            .iter()
+            .filter(|aka| aka.country_code() == Some(country.as_str()))
+            .collect::<Vec<&Aka>>();
+        akas.iter()
+            .find(|aka| !aka.is_secondary())
+            .or_else(|| akas.first())
+            .map(|aka| aka.title())
+    }
+}
+
+impl By for ByReleaseInfo {
+    type ParseResult = ReleaseInfo;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/title/{}/releaseinfo",
+                engine.base_uri(),
+                imdb_id("tt", query)?
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let releases = html
+            .select_all("table.release-dates-table-test-only tr.release-date-item")
+            .into_iter()
+            .map(ByReleaseInfo::parse_release);
+        let (releases, mut warnings) = collect_items(self.lenient, releases)?;
+
+        let akas = html
+            .select_all("table.akas-table-test-only tr.aka-item")
+            .into_iter()
+            .map(ByReleaseInfo::parse_aka);
+        let (mut akas, aka_warnings) = collect_items(self.lenient, akas)?;
+        warnings.extend(aka_warnings);
+
+        let codes = releases
+            .iter()
+            .filter_map(|r| Some((r.country.as_str(), r.country_code.clone()?)))
+            .collect::<HashMap<&str, String>>();
+        for aka in akas.iter_mut() {
+            aka.country_code = aka.country.as_deref().and_then(|country| {
+                codes
+                    .get(country)
+                    .cloned()
+                    .or_else(|| country_code_of(country).map(str::to_string))
+            });
+        }
+
+        Ok(ReleaseInfo {
+            releases,
+            akas,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::filters::Date;
+    use crate::search::By;
+
+    use super::{ByReleaseInfo, ReleaseAttribute};
+
+    const RELEASE_INFO_PAGE: &str = r#"
+<h4 class="ipl-header__content ipl-list-title">Release Dates</h4>
+<table class="ipl-zebra-list ipl-zebra-list--fixed-first release-dates-table-test-only">
+    <tr class="ipl-zebra-list__item release-date-item">
+        <td class="release-date-item__country-name"><a href="/calendar/?region=us&ref_=ttrel_rel_1">United States</a></td>
+        <td class="release-date-item__date" align="right">25 May 1977</td>
+        <td class="release-date-item__attributes">(Oakland, California)
+             (premiere)</td>
+    </tr>
+    <tr class="ipl-zebra-list__item release-date-item">
+        <td class="release-date-item__country-name"><a href="/calendar/?region=fr&ref_=ttrel_rel_2">France</a></td>
+        <td class="release-date-item__date" align="right">15 May 1977</td>
+        <td class="release-date-item__attributes">(Cannes Film Festival)</td>
+    </tr>
+    <tr class="ipl-zebra-list__item release-date-item">
+        <td class="release-date-item__country-name"><a href="/calendar/?region=ca&ref_=ttrel_rel_3">Canada</a></td>
+        <td class="release-date-item__date" align="right">1977</td>
+        <td class="release-date-item__attributes--empty"></td>
+    </tr>
+</table>
+<h4 class="ipl-header__content ipl-list-title">Also Known As (AKA)</h4>
+<table class="ipl-zebra-list akas-table-test-only">
+    <tr class="ipl-zebra-list__item aka-item">
+        <td class="aka-item__name">(original title)</td>
+        <td class="aka-item__title">Star Wars</td>
+    </tr>
+    <tr class="ipl-zebra-list__item aka-item">
+        <td class="aka-item__name">Canada (French title)</td>
+        <td class="aka-item__title">La guerre des étoiles</td>
+    </tr>
+    <tr class="ipl-zebra-list__item aka-item">
+        <td class="aka-item__name">France (working title)</td>
+        <td class="aka-item__title">Guerre des étoiles, épisode IV</td>
+    </tr>
+    <tr class="ipl-zebra-list__item aka-item">
+        <td class="aka-item__name">France</td>
+        <td class="aka-item__title">La guerre des étoiles</td>
+    </tr>
+    <tr class="ipl-zebra-list__item aka-item">
+        <td class="aka-item__name">Argentina</td>
+        <td class="aka-item__title">La guerra de las galaxias</td>
+    </tr>
+</table>
+"#;
+
+    #[test]
+    fn test_release_info_parser() {
+        let info = ByReleaseInfo::default()
+            .parse_result(Html::parse_document(RELEASE_INFO_PAGE))
+            .unwrap();
+
+        assert_eq!(info.releases().len(), 3);
+        let us = &info.releases()[0];
+        assert_eq!(us.country(), "United States");
+        assert_eq!(us.country_code(), Some("us"));
+        assert_eq!(us.date(), Some(Date::new(1977, 5, 25).unwrap()));
+        assert!(us.is_premiere());
+        assert_eq!(
+            us.attributes()[0],
+            ReleaseAttribute::Other("Oakland, California".to_string())
+        );
+        assert_eq!(info.releases()[1].festival(), Some("Cannes Film Festival"));
+        assert!(info.releases()[2].attributes().is_empty());
+
+        assert_eq!(info.akas().len(), 5);
+        assert_eq!(info.original_title(), Some("Star Wars"));
+        assert_eq!(info.akas()[0].country(), None);
+        let canada = &info.akas()[1];
+        assert_eq!(canada.country(), Some("Canada"));
+        assert_eq!(canada.country_code(), Some("ca"));
+        assert_eq!(canada.language(), Some("French"));
+        assert_eq!(info.akas()[2].language(), None);
+
+        assert_eq!(info.localised_title("FR"), Some("La guerre des étoiles"));
+        assert_eq!(info.localised_title("ca"), Some("La guerre des étoiles"));
+        assert_eq!(info.localised_title("de"), None);
+        // Argentina has no release date listed.
+        assert_eq!(info.akas()[4].country_code(), Some("ar"));
+        assert_eq!(
+            info.localised_title("ar"),
+            Some("La guerra de las galaxias")
+        );
+    }
+}
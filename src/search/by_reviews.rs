@@ -0,0 +1,404 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html, Node};
+use urlencoding::encode;
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::{imdb_id, Date, ReviewSortOrder};
+use super::{parse_uri, By, Paginated};
+
+/// Loads the user reviews of a title, `/title/{id}/reviews`, the query being
+/// the id like `tt0076759`.
+///
+/// Later pages are loaded the way the "Load More" button does, see
+/// [`Reviews::next_query`].
+#[derive(Debug, Clone, Default)]
+pub struct ByReviews {
+    sort: Option<ReviewSortOrder>,
+    rating: Option<u8>,
+    hide_spoilers: bool,
+    pagination_key: Option<String>,
+    lenient: bool,
+}
+
+impl ByReviews {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sort(mut self, sort: ReviewSortOrder) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Only reviews rating the title `stars` out of 10.
+    pub fn rating(mut self, stars: u8) -> Self {
+        self.rating = Some(stars);
+        self
+    }
+
+    pub fn hide_spoilers(mut self, hide_spoilers: bool) -> Self {
+        self.hide_spoilers = hide_spoilers;
+        self
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    // <div class="lister-item imdb-user-review" data-review-id="rw0135585">
+    //     <span class="rating-other-user-rating"><span>10</span><span class="point-scale">/10</span></span>
+    //     <a href="/review/rw0135585/" class="title"> The Force is strong </a>
+    //     <div class="display-name-date">
+    //         <span class="display-name-link"><a href="/user/ur0361926/">beth-97</a></span>
+    //         <span class="review-date">25 May 1999</span>
+    //     </div>
+    //     <span class="spoiler-warning">Warning: Spoilers</span>
+    //     <div class="content">
+    //         <div class="text show-more__control">...</div>
+    //         <div class="actions text-muted">123 out of 150 found this helpful. ...</div>
+    //     </div>
+    // </div>
+    fn parse_review(item: ElementRef) -> Result<Review, ParseError> {
+        let body = item.require_first("div.content div.text")?;
+        let text = |selector: &str| {
+            item.select_first(selector)
+                .map(|e| e.text_content())
+                .filter(|t| !t.is_empty())
+        };
+
+        // "123 out of 150 found this helpful."
+        let helpful = text("div.actions").and_then(|actions| {
+            let words = actions
+                .split_whitespace()
+                .map(|w| w.replace(',', ""))
+                .collect::<Vec<String>>();
+            let out_of = words.iter().position(|w| w == "out")?;
+            let helpful = words.get(out_of.checked_sub(1)?)?.parse().ok()?;
+            let total = words.get(out_of + 2)?.parse().ok()?;
+            Some((helpful, total))
+        });
+
+        Ok(Review {
+            id: item.require_attr("data-review-id")?.to_string(),
+            // Deleted accounts are shown without a link.
+            author: item.select_first("span.display-name-link a").and_then(|a| {
+                Some(ATag {
+                    text: a.text_content(),
+                    link: a.value().attr("href")?.to_string(),
+                })
+            }),
+            date: text("span.review-date").and_then(|d| Date::parse_long(&d)),
+            rating: text("span.rating-other-user-rating > span").and_then(|r| r.parse().ok()),
+            headline: text("a.title").unwrap_or_default(),
+            body: Self::parse_body(body),
+            spoiler: item.select_first("span.spoiler-warning").is_some(),
+            helpful,
+        })
+    }
+
+    /// One paragraph per line, as split by the `<br>`s of the page.
+    fn parse_body(body: ElementRef) -> String {
+        let mut paragraphs = vec![];
+        let mut paragraph = String::new();
+        for child in body.children() {
+            match child.value() {
+                Node::Element(e) if e.name() == "br" => {
+                    paragraphs.push(std::mem::take(&mut paragraph))
+                }
+                Node::Element(_) => {
+                    if let Some(element) = ElementRef::wrap(child) {
+                        paragraph.extend(element.text());
+                    }
+                }
+                Node::Text(text) => paragraph.push_str(text),
+                _ => {}
+            }
+        }
+        paragraphs.push(paragraph);
+
+        paragraphs
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+}
+
+pub struct Review {
+    id: String,
+    author: Option<ATag>,
+    date: Option<Date>,
+    rating: Option<u8>,
+    headline: String,
+    body: String,
+    spoiler: bool,
+    helpful: Option<(u32, u32)>,
+}
+
+impl Review {
+    /// Like `rw0135585`.
+    pub fn id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    /// `None` for reviews of deleted accounts.
+    pub fn author(&self) -> Option<&ATag> {
+        self.author.as_ref()
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    /// Out of 10, `None` when the author did not rate the title.
+    pub fn rating(&self) -> Option<u8> {
+        self.rating
+    }
+
+    pub fn headline(&self) -> &str {
+        self.headline.as_ref()
+    }
+
+    pub fn body(&self) -> &str {
+        self.body.as_ref()
+    }
+
+    pub fn spoiler(&self) -> bool {
+        self.spoiler
+    }
+
+    /// Users who found the review helpful.
+    pub fn helpful_votes(&self) -> Option<u32> {
+        self.helpful.map(|(helpful, _)| helpful)
+    }
+
+    /// Users who voted on the helpfulness of the review.
+    pub fn total_votes(&self) -> Option<u32> {
+        self.helpful.map(|(_, total)| total)
+    }
+}
+
+pub struct Reviews {
+    items: Vec<Review>,
+    warnings: Vec<ParseError>,
+    total_reviews: Option<u32>,
+    pagination_key: Option<String>,
+    query: ByReviews,
+}
+
+impl Reviews {
+    pub fn items(&self) -> &[Review] {
+        self.items.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+
+    /// From the "1,234 Reviews" header, only on the first page.
+    pub fn total_reviews(&self) -> Option<u32> {
+        self.total_reviews
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.pagination_key.is_some()
+    }
+
+    /// The `ByReviews` for the following page, keeping every other option.
+    pub fn next_query(&self) -> Option<ByReviews> {
+        Some(ByReviews {
+            pagination_key: Some(self.pagination_key.clone()?),
+            ..self.query.clone()
+        })
+    }
+}
+
+impl By for ByReviews {
+    type ParseResult = Reviews;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        let mut params = String::new();
+        if let Some(rating) = self.rating {
+            if !(1..=10).contains(&rating) {
+                return Err(ImdbError::InvalidQuery(
+                    "review rating must be between 1 and 10".to_string(),
+                ));
+            }
+            params.push_str(&format!("&ratingFilter={}", rating));
+        }
+        if self.hide_spoilers {
+            params.push_str("&spoiler=hide");
+        }
+        if let Some(sort) = self.sort {
+            params.push_str(&sort.to_query_string());
+        }
+        if let Some(key) = &self.pagination_key {
+            params.push_str(&format!("&paginationKey={}", encode(key)));
+        }
+
+        parse_uri(
+            format!(
+                "{}/title/{}/reviews{}?{}",
+                engine.base_uri(),
+                imdb_id("tt", query)?,
+                // Where the "Load More" button gets the following pages from.
+                if self.pagination_key.is_some() {
+                    "/_ajax"
+                } else {
+                    ""
+                },
+                params.trim_start_matches('&')
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let reviews = html
+            .select_all("div.lister-list > div.imdb-user-review")
+            .into_iter()
+            .map(ByReviews::parse_review);
+        let (items, warnings) = collect_items(self.lenient, reviews)?;
+
+        Ok(Reviews {
+            items,
+            warnings,
+            total_reviews: html.select_first("div.header span").and_then(|s| {
+                s.text_content()
+                    .split_whitespace()
+                    .next()?
+                    .replace(',', "")
+                    .parse()
+                    .ok()
+            }),
+            pagination_key: html
+                .select_first("div.load-more-data")
+                .and_then(|d| d.value().attr("data-key"))
+                .filter(|key| !key.is_empty())
+                .map(|key| key.to_string()),
+            query: self.clone(),
+        })
+    }
+}
+
+impl Paginated for ByReviews {
+    type Item = Review;
+
+    fn next_page(&self, result: &Reviews) -> Option<Self> {
+        result.next_query()
+    }
+
+    fn into_items(result: Reviews) -> Vec<Review> {
+        result.items
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::prelude::ImdbSearchEngine;
+    use crate::search::filters::{Date, ReviewSortField, ReviewSortOrder};
+    use crate::search::By;
+
+    use super::ByReviews;
+
+    const REVIEWS_PAGE: &str = r#"
+<div class="lister">
+    <div class="header"><div><span>1,234 Reviews</span></div></div>
+    <div class="lister-list">
+        <div class="lister-item mode-detail imdb-user-review collapsable" data-review-id="rw0135585">
+            <div class="review-container">
+                <div class="lister-item-content">
+                    <div class="ipl-ratings-bar">
+                        <span class="rating-other-user-rating"><span>10</span><span class="point-scale">/10</span></span>
+                    </div>
+                    <a href="/review/rw0135585/" class="title"> The Force is strong with this one
+</a>
+                    <div class="display-name-date">
+                        <span class="display-name-link"><a href="/user/ur0361926/">beth-97</a></span>
+                        <span class="review-date">25 May 1999</span>
+                    </div>
+                    <span class="spoiler-warning">Warning: Spoilers</span>
+                    <div class="content">
+                        <div class="text show-more__control">A great adventure.<br><br>Vader is the best <i>villain</i> of all time.</div>
+                        <div class="actions text-muted">
+                            1,023 out of 1,150 found this helpful.
+                            <span>Was this review helpful? <a href="/registration/signin">Sign in</a> to vote.</span>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        </div>
+        <div class="lister-item mode-detail imdb-user-review collapsable" data-review-id="rw0135586">
+            <div class="review-container">
+                <div class="lister-item-content">
+                    <a href="/review/rw0135586/" class="title"> Overrated</a>
+                    <div class="display-name-date">
+                        <span class="display-name-link">Deleted user</span>
+                        <span class="review-date">2 June 2001</span>
+                    </div>
+                    <div class="content"><div class="text show-more__control">Not for me.</div></div>
+                </div>
+            </div>
+        </div>
+    </div>
+    <div class="load-more-data" data-key="g4wp7crmqizdeyyf72ux5nrurdsmqbzjwfhr" data-ajaxurl="/title/tt0076759/reviews/_ajax"></div>
+</div>
+"#;
+
+    #[test]
+    fn test_reviews_parser() {
+        let by = ByReviews::new()
+            .sort(ReviewSortOrder::desc(ReviewSortField::Date))
+            .hide_spoilers(true);
+        let result = by.parse_result(Html::parse_document(REVIEWS_PAGE)).unwrap();
+
+        assert_eq!(result.total_reviews(), Some(1234));
+        assert_eq!(result.items().len(), 2);
+
+        let first = &result.items()[0];
+        assert_eq!(first.id(), "rw0135585");
+        assert_eq!(first.author().unwrap().text(), "beth-97");
+        assert_eq!(first.date(), Some(Date::new(1999, 5, 25).unwrap()));
+        assert_eq!(first.rating(), Some(10));
+        assert_eq!(first.headline(), "The Force is strong with this one");
+        assert_eq!(
+            first.body(),
+            "A great adventure.\nVader is the best villain of all time."
+        );
+        assert!(first.spoiler());
+        assert_eq!(first.helpful_votes(), Some(1023));
+        assert_eq!(first.total_votes(), Some(1150));
+
+        let second = &result.items()[1];
+        assert!(second.author().is_none());
+        assert_eq!(second.rating(), None);
+        assert!(!second.spoiler());
+        assert_eq!(second.helpful_votes(), None);
+
+        let engine = ImdbSearchEngine::new();
+        assert_eq!(
+            by.get_uri(&engine, "tt0076759").unwrap().to_string(),
+            "https://www.imdb.com/title/tt0076759/reviews?spoiler=hide&sort=submissionDate&dir=desc"
+        );
+        let next = result.next_query().unwrap();
+        assert_eq!(
+            next.get_uri(&engine, "tt0076759").unwrap().to_string(),
+            "https://www.imdb.com/title/tt0076759/reviews/_ajax?spoiler=hide\
+             &sort=submissionDate&dir=desc&paginationKey=g4wp7crmqizdeyyf72ux5nrurdsmqbzjwfhr"
+        );
+        assert!(ByReviews::new()
+            .rating(11)
+            .get_uri(&engine, "tt0076759")
+            .is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::{
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper, link_id,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::{imdb_id, Date};
+use super::{parse_uri, By};
+
+/// Loads one season of a series, `/title/{id}/episodes?season={n}`, the query
+/// being the series id like `tt0944947`.
+///
+/// Without a season IMDb picks one, [`Season::season`] tells which. See
+/// [`ImdbSearchEngine::all_seasons`] to get every season at once.
+#[derive(Debug, Clone, Default)]
+pub struct BySeason {
+    season: Option<SeasonNumber>,
+    lenient: bool,
+}
+
+impl BySeason {
+    pub fn new(season: u16) -> Self {
+        Self::of(SeasonNumber::Numbered(season))
+    }
+
+    /// The episodes IMDb has not put in any season.
+    pub fn unknown() -> Self {
+        Self::of(SeasonNumber::Unknown)
+    }
+
+    pub fn of(season: SeasonNumber) -> Self {
+        Self {
+            season: Some(season),
+            lenient: false,
+        }
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    // <div class="list_item odd">
+    //     <div class="image"><a href="/title/tt1480055/"><div><img src="..."><div>S1, Ep1</div></div></a></div>
+    //     <div class="info">
+    //         <meta itemprop="episodeNumber" content="1"/>
+    //         <div class="airdate">17 Apr. 2011</div>
+    //         <strong><a href="/title/tt1480055/" itemprop="name">Winter Is Coming</a></strong>
+    //         <div class="ipl-rating-widget">
+    //             <span class="ipl-rating-star__rating">8.9</span>
+    //             <span class="ipl-rating-star__total-votes">(48,202)</span>
+    //         </div>
+    //         <div class="item_description" itemprop="description">Eddard Stark is torn ...</div>
+    //     </div>
+    // </div>
+    fn parse_episode(
+        item: ElementRef,
+        season: Option<SeasonNumber>,
+    ) -> Result<Episode, ParseError> {
+        let info = item.require_first("div.info")?;
+        let title = info.require_first(r#"strong > a"#)?;
+        let number = info.require_first(r#"meta[itemprop="episodeNumber"]"#)?;
+        let text = |selector: &str| {
+            info.select_first(selector)
+                .map(|e| e.text_content())
+                .filter(|t| !t.is_empty())
+        };
+
+        Ok(Episode {
+            season,
+            episode: number
+                .require_attr("content")?
+                .parse()
+                .map_err(|_| ParseError::new(r#"meta[itemprop="episodeNumber"]"#, &info))?,
+            title: ATag {
+                text: title.text_content(),
+                link: title.require_attr("href")?.to_string(),
+            },
+            air_date: text("div.airdate").and_then(|d| Date::parse_long(&d)),
+            rating: text("span.ipl-rating-star__rating").and_then(|r| r.parse().ok()),
+            votes: text("span.ipl-rating-star__total-votes").and_then(|v| {
+                v.trim_matches(|c| c == '(' || c == ')')
+                    .replace(',', "")
+                    .parse()
+                    .ok()
+            }),
+            // Episodes without a plot say so, with a link to add one.
+            plot: text("div.item_description")
+                .filter(|p| !p.starts_with("Know what this is about?")),
+            thumbnail: item
+                .select_first("div.image img")
+                .and_then(|img| Some(img.value().attr("src")?.to_string())),
+        })
+    }
+}
+
+/// A season as listed in the season picker of the episodes page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeasonNumber {
+    Numbered(u16),
+    /// Where series with unnumbered episodes put them, `-1` in urls.
+    Unknown,
+}
+
+impl SeasonNumber {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "-1" => Some(Self::Unknown),
+            value => value.parse().ok().map(Self::Numbered),
+        }
+    }
+
+    fn as_param(&self) -> String {
+        match self {
+            Self::Numbered(season) => season.to_string(),
+            Self::Unknown => "-1".to_string(),
+        }
+    }
+}
+
+pub struct Episode {
+    season: Option<SeasonNumber>,
+    episode: u16,
+    title: ATag,
+    air_date: Option<Date>,
+    rating: Option<f32>,
+    votes: Option<u32>,
+    plot: Option<String>,
+    thumbnail: Option<String>,
+}
+
+impl Episode {
+    pub fn get_title_id(&self) -> Option<String> {
+        link_id(&self.title.link)
+    }
+
+    pub fn season(&self) -> Option<SeasonNumber> {
+        self.season
+    }
+
+    pub fn episode(&self) -> u16 {
+        self.episode
+    }
+
+    pub fn title(&self) -> &ATag {
+        &self.title
+    }
+
+    /// `None` for episodes that are yet to be announced.
+    pub fn air_date(&self) -> Option<Date> {
+        self.air_date
+    }
+
+    pub fn rating(&self) -> Option<f32> {
+        self.rating
+    }
+
+    pub fn votes(&self) -> Option<u32> {
+        self.votes
+    }
+
+    pub fn plot(&self) -> Option<&str> {
+        self.plot.as_deref()
+    }
+
+    pub fn thumbnail(&self) -> Option<&str> {
+        self.thumbnail.as_deref()
+    }
+}
+
+pub struct Season {
+    season: Option<SeasonNumber>,
+    seasons: Vec<SeasonNumber>,
+    episodes: Vec<Episode>,
+    warnings: Vec<ParseError>,
+}
+
+impl Season {
+    /// The season selected in the page, else the one asked for.
+    pub fn season(&self) -> Option<SeasonNumber> {
+        self.season
+    }
+
+    /// Every season of the series, from the season picker of the page,
+    /// [`SeasonNumber::Unknown`] included when the series has one.
+    pub fn seasons(&self) -> &[SeasonNumber] {
+        self.seasons.as_ref()
+    }
+
+    pub fn episodes(&self) -> &[Episode] {
+        self.episodes.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+}
+
+impl By for BySeason {
+    type ParseResult = Season;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/title/{}/episodes{}",
+                engine.base_uri(),
+                imdb_id("tt", query)?,
+                self.season
+                    .map(|season| format!("?season={}", season.as_param()))
+                    .unwrap_or_default()
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let options = html.select_all("#bySeason option");
+        let season = options
+            .iter()
+            .find(|o| o.value().attr("selected").is_some())
+            .and_then(|o| SeasonNumber::parse(o.value().attr("value")?))
+            .or(self.season);
+
+        let episodes = html
+            .select_all("div.list.detail.eplist > div.list_item")
+            .into_iter()
+            .map(|item| BySeason::parse_episode(item, season));
+        let (episodes, warnings) = collect_items(self.lenient, episodes)?;
+
+        Ok(Season {
+            season,
+            seasons: options
+                .iter()
+                .filter_map(|o| SeasonNumber::parse(o.value().attr("value")?))
+                .collect(),
+            episodes,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::prelude::ImdbSearchEngine;
+    use crate::search::filters::Date;
+    use crate::search::By;
+
+    use super::{BySeason, SeasonNumber};
+
+    const SEASON_PAGE: &str = r#"
+<select id="bySeason" tconst="tt0944947">
+    <option value="1">1</option>
+    <option selected="selected" value="2">2</option>
+    <option value="-1">Unknown</option>
+</select>
+<div class="list detail eplist">
+    <div class="list_item odd">
+        <div class="image">
+            <a href="/title/tt1668746/" title="The North Remembers">
+                <div class="hover-over-image zero-z-index" data-const="tt1668746">
+                    <img alt="The North Remembers" src="https://m.media-amazon.com/images/M/north.jpg">
+                    <div>S2, Ep1</div>
+                </div>
+            </a>
+        </div>
+        <div class="info" itemprop="episodes">
+            <meta itemprop="episodeNumber" content="1"/>
+            <div class="airdate">
+                1 Apr. 2012
+            </div>
+            <strong><a href="/title/tt1668746/" title="The North Remembers" itemprop="name">The North Remembers</a></strong>
+            <div class="ipl-rating-widget">
+                <div class="ipl-rating-star small">
+                    <span class="ipl-rating-star__rating">8.6</span>
+                    <span class="ipl-rating-star__total-votes">(40,131)</span>
+                </div>
+            </div>
+            <div class="item_description" itemprop="description">
+                Tyrion arrives at King's Landing to take his father's place as Hand of the King.
+            </div>
+        </div>
+    </div>
+    <div class="list_item even">
+        <div class="image"><a href="/title/tt9999999/"><div><div>S2, Ep2</div></div></a></div>
+        <div class="info" itemprop="episodes">
+            <meta itemprop="episodeNumber" content="2"/>
+            <div class="airdate">2012</div>
+            <strong><a href="/title/tt9999999/" itemprop="name">Episode #2.2</a></strong>
+            <div class="item_description" itemprop="description">
+                Know what this is about? <a href="/title/tt9999999/plotsummary">Be the first one to add a plot.</a>
+            </div>
+        </div>
+    </div>
+</div>
+"#;
+
+    #[test]
+    fn test_season_parser() {
+        let by = BySeason::new(2);
+        let season = by.parse_result(Html::parse_document(SEASON_PAGE)).unwrap();
+
+        assert_eq!(season.season(), Some(SeasonNumber::Numbered(2)));
+        // The page tells which season it shows when none was asked for.
+        let picked = BySeason::default()
+            .parse_result(Html::parse_document(SEASON_PAGE))
+            .unwrap();
+        assert_eq!(picked.season(), Some(SeasonNumber::Numbered(2)));
+        assert_eq!(
+            season.seasons(),
+            [
+                SeasonNumber::Numbered(1),
+                SeasonNumber::Numbered(2),
+                SeasonNumber::Unknown
+            ]
+        );
+        assert_eq!(season.episodes().len(), 2);
+
+        let first = &season.episodes()[0];
+        assert_eq!(first.season(), Some(SeasonNumber::Numbered(2)));
+        assert_eq!(first.episode(), 1);
+        assert_eq!(first.get_title_id().as_deref(), Some("tt1668746"));
+        assert_eq!(first.title().text(), "The North Remembers");
+        assert_eq!(first.air_date(), Some(Date::new(2012, 4, 1).unwrap()));
+        assert_eq!(first.rating(), Some(8.6));
+        assert_eq!(first.votes(), Some(40131));
+        assert!(first.plot().unwrap().starts_with("Tyrion arrives"));
+        assert_eq!(
+            first.thumbnail(),
+            Some("https://m.media-amazon.com/images/M/north.jpg")
+        );
+
+        let second = &season.episodes()[1];
+        assert_eq!(second.air_date(), Some(Date::year(2012)));
+        assert_eq!(second.rating(), None);
+        assert_eq!(second.plot(), None);
+        assert_eq!(second.thumbnail(), None);
+
+        let engine = ImdbSearchEngine::new();
+        let uri = |by: BySeason| by.get_uri(&engine, "tt0944947").unwrap().to_string();
+        assert_eq!(
+            uri(by),
+            "https://www.imdb.com/title/tt0944947/episodes?season=2"
+        );
+        assert_eq!(
+            uri(BySeason::unknown()),
+            "https://www.imdb.com/title/tt0944947/episodes?season=-1"
+        );
+        assert_eq!(
+            uri(BySeason::default()),
+            "https://www.imdb.com/title/tt0944947/episodes"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html, Node};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::{
+    element_parser::ElementParserHelper, get_selector, html_parser::HtmlParserHelper, parenthesised,
+};
+use crate::ImdbSearchEngine;
+
+use super::filters::imdb_id;
+use super::{parse_uri, By};
+
+/// Loads the technical specifications of a title, `/title/{id}/technical`,
+/// the query being the id like `tt0076759`.
+#[derive(Debug, Clone, Default)]
+pub struct ByTechnicalSpecs;
+
+/// `text` without its `(...)` groups, `Deluxe, Hollywood (CA), USA (color)`
+/// reading `Deluxe, Hollywood, USA`.
+fn outside_parentheses(text: &str) -> String {
+    let mut depth = 0;
+    let mut outside = String::new();
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            // A stray `)` must not hide the rest of the text.
+            ')' => depth = (depth - 1).max(0),
+            c if depth == 0 => outside.push(c),
+            _ => {}
+        }
+    }
+
+    outside
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace(" ,", ",")
+}
+
+impl ByTechnicalSpecs {
+    /// One value per line or `|` separated part of the cell.
+    // <td>
+    //     <a href="/search/title?sound_mixes=dolby">Dolby</a> (as Dolby System)<br>
+    //     | <a href="/search/title?sound_mixes=6_track">6-Track</a> (70 mm prints)
+    // </td>
+    fn parse_values(td: ElementRef) -> Vec<SpecValue> {
+        let mut lines = vec![];
+        let mut line = String::new();
+        for child in td.children() {
+            match child.value() {
+                Node::Element(e) if e.name() == "br" => lines.push(std::mem::take(&mut line)),
+                Node::Element(_) => {
+                    if let Some(element) = ElementRef::wrap(child) {
+                        line.push_str(&element.text_content());
+                    }
+                }
+                Node::Text(text) => line.push_str(text),
+                _ => {}
+            }
+        }
+        lines.push(line);
+
+        lines
+            .iter()
+            .flat_map(|line| line.split('|'))
+            .map(|part| part.split_whitespace().collect::<Vec<&str>>().join(" "))
+            .filter(|part| !part.is_empty())
+            .map(|part| SpecValue {
+                value: outside_parentheses(&part),
+                notes: parenthesised(&part),
+            })
+            .collect()
+    }
+}
+
+/// A value with the notes IMDb puts in parentheses after it.
+pub struct SpecValue {
+    value: String,
+    notes: Vec<String>,
+}
+
+impl SpecValue {
+    /// Like `Dolby` or `2.39 : 1`.
+    pub fn value(&self) -> &str {
+        self.value.as_ref()
+    }
+
+    /// Like `70 mm prints` or `as Dolby System`.
+    pub fn notes(&self) -> &[String] {
+        self.notes.as_ref()
+    }
+}
+
+pub struct Runtime {
+    minutes: Option<u32>,
+    notes: Vec<String>,
+}
+
+impl Runtime {
+    pub fn minutes(&self) -> Option<u32> {
+        self.minutes
+    }
+
+    /// The edition or country of that runtime, like `special edition`.
+    pub fn notes(&self) -> &[String] {
+        self.notes.as_ref()
+    }
+}
+
+impl From<SpecValue> for Runtime {
+    // 2 hr 1 min (121 min) (special edition)
+    fn from(spec: SpecValue) -> Self {
+        let minutes = |text: &str| text.strip_suffix(" min")?.trim().parse().ok();
+        let (in_minutes, notes) = spec
+            .notes
+            .into_iter()
+            .partition::<Vec<String>, _>(|note| minutes(note).is_some());
+
+        Runtime {
+            minutes: in_minutes
+                .first()
+                .and_then(|note| minutes(note))
+                .or_else(|| minutes(&spec.value)),
+            notes,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TechnicalSpecs {
+    runtimes: Vec<Runtime>,
+    sound_mixes: Vec<SpecValue>,
+    colors: Vec<SpecValue>,
+    aspect_ratios: Vec<SpecValue>,
+    cameras: Vec<SpecValue>,
+    laboratories: Vec<SpecValue>,
+    negative_formats: Vec<SpecValue>,
+    cinematographic_processes: Vec<SpecValue>,
+    printed_film_formats: Vec<SpecValue>,
+}
+
+impl TechnicalSpecs {
+    pub fn runtimes(&self) -> &[Runtime] {
+        self.runtimes.as_ref()
+    }
+
+    pub fn sound_mixes(&self) -> &[SpecValue] {
+        self.sound_mixes.as_ref()
+    }
+
+    pub fn colors(&self) -> &[SpecValue] {
+        self.colors.as_ref()
+    }
+
+    pub fn aspect_ratios(&self) -> &[SpecValue] {
+        self.aspect_ratios.as_ref()
+    }
+
+    pub fn cameras(&self) -> &[SpecValue] {
+        self.cameras.as_ref()
+    }
+
+    pub fn laboratories(&self) -> &[SpecValue] {
+        self.laboratories.as_ref()
+    }
+
+    pub fn negative_formats(&self) -> &[SpecValue] {
+        self.negative_formats.as_ref()
+    }
+
+    pub fn cinematographic_processes(&self) -> &[SpecValue] {
+        self.cinematographic_processes.as_ref()
+    }
+
+    pub fn printed_film_formats(&self) -> &[SpecValue] {
+        self.printed_film_formats.as_ref()
+    }
+}
+
+impl By for ByTechnicalSpecs {
+    type ParseResult = TechnicalSpecs;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
+            format!(
+                "{}/title/{}/technical",
+                engine.base_uri(),
+                imdb_id("tt", query)?
+            )
+            .as_str(),
+        )
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let table = html.require_first("table.dataTable.labelValueTable")?;
+        let mut specs = TechnicalSpecs::default();
+
+        // <tr class="odd"><td class="label"> Runtime </td><td>2 hr 1 min (121 min)</td></tr>
+        let value_td = get_selector("td:not(.label)");
+        for tr in table.select_all("tr") {
+            let (label, td) = match (tr.select_first("td.label"), tr.select(&value_td).next()) {
+                (Some(label), Some(td)) => (label.text_content(), td),
+                _ => continue,
+            };
+            let values = ByTechnicalSpecs::parse_values(td);
+            match label.as_str() {
+                "Runtime" => specs.runtimes = values.into_iter().map(Runtime::from).collect(),
+                "Sound Mix" => specs.sound_mixes = values,
+                "Color" => specs.colors = values,
+                "Aspect Ratio" => specs.aspect_ratios = values,
+                "Camera" => specs.cameras = values,
+                "Laboratory" => specs.laboratories = values,
+                "Negative Format" => specs.negative_formats = values,
+                "Cinematographic Process" => specs.cinematographic_processes = values,
+                "Printed Film Format" => specs.printed_film_formats = values,
+                _ => {}
+            }
+        }
+
+        Ok(specs)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::By;
+
+    use super::{outside_parentheses, ByTechnicalSpecs};
+
+    const TECHNICAL_PAGE: &str = r#"
+<table class="dataTable labelValueTable">
+    <tbody>
+        <tr class="odd">
+            <td class="label"> Runtime </td>
+            <td>
+                2 hr 1 min (121 min)
+                <br>
+                2 hr 5 min (125 min) (special edition)
+            </td>
+        </tr>
+        <tr class="even">
+            <td class="label"> Sound Mix </td>
+            <td>
+                <a href="/search/title?sound_mixes=dolby">Dolby</a> (as Dolby System)
+                <br>
+                | <a href="/search/title?sound_mixes=6_track">6-Track</a> (70 mm prints)
+                | <a href="/search/title?sound_mixes=mono">Mono</a> (some 35 mm prints)
+            </td>
+        </tr>
+        <tr class="odd">
+            <td class="label"> Color </td>
+            <td><a href="/search/title?colors=color">Color</a> (Technicolor)</td>
+        </tr>
+        <tr class="even">
+            <td class="label"> Aspect Ratio </td>
+            <td>2.39 : 1<br>2.20 : 1 (70 mm prints)</td>
+        </tr>
+        <tr class="odd">
+            <td class="label"> Camera </td>
+            <td>Panavision Panaflex Camera, Panavision Lenses<br>Mitchell BNC, Panavision Lenses (additional scenes)</td>
+        </tr>
+        <tr class="even">
+            <td class="label"> Laboratory </td>
+            <td>Deluxe, Hollywood (CA), USA (color)</td>
+        </tr>
+        <tr class="odd">
+            <td class="label"> Film Length </td>
+            <td>3,322 m (12 reels)</td>
+        </tr>
+        <tr class="even">
+            <td class="label"> Negative Format </td>
+            <td>35 mm (Eastman 100T 5247)</td>
+        </tr>
+        <tr class="odd">
+            <td class="label"> Cinematographic Process </td>
+            <td>Dykstraflex (visual effects)<br>Panavision (anamorphic)</td>
+        </tr>
+        <tr class="even">
+            <td class="label"> Printed Film Format </td>
+            <td>35 mm<br>70 mm (blow-up)</td>
+        </tr>
+    </tbody>
+</table>
+"#;
+
+    #[test]
+    fn test_technical_specs_parser() {
+        let specs = ByTechnicalSpecs
+            .parse_result(Html::parse_document(TECHNICAL_PAGE))
+            .unwrap();
+
+        assert_eq!(specs.runtimes().len(), 2);
+        assert_eq!(specs.runtimes()[0].minutes(), Some(121));
+        assert!(specs.runtimes()[0].notes().is_empty());
+        assert_eq!(specs.runtimes()[1].minutes(), Some(125));
+        assert_eq!(specs.runtimes()[1].notes(), ["special edition"]);
+
+        let mixes = specs
+            .sound_mixes()
+            .iter()
+            .map(|m| m.value())
+            .collect::<Vec<&str>>();
+        assert_eq!(mixes, ["Dolby", "6-Track", "Mono"]);
+        assert_eq!(specs.sound_mixes()[0].notes(), ["as Dolby System"]);
+
+        assert_eq!(specs.colors()[0].value(), "Color");
+        assert_eq!(specs.colors()[0].notes(), ["Technicolor"]);
+        assert_eq!(specs.aspect_ratios().len(), 2);
+        assert_eq!(specs.aspect_ratios()[1].value(), "2.20 : 1");
+        assert_eq!(specs.aspect_ratios()[1].notes(), ["70 mm prints"]);
+        assert_eq!(specs.cameras().len(), 2);
+        assert_eq!(
+            specs.cameras()[0].value(),
+            "Panavision Panaflex Camera, Panavision Lenses"
+        );
+        assert_eq!(specs.laboratories()[0].value(), "Deluxe, Hollywood, USA");
+        assert_eq!(specs.laboratories()[0].notes(), ["CA", "color"]);
+        assert_eq!(specs.negative_formats()[0].value(), "35 mm");
+        assert_eq!(specs.cinematographic_processes().len(), 2);
+        assert_eq!(specs.printed_film_formats()[1].notes(), ["blow-up"]);
+    }
+
+    #[test]
+    fn test_stray_closing_parenthesis() {
+        assert_eq!(outside_parentheses("35 mm) (blow-up) 70 mm"), "35 mm 70 mm");
+    }
+}
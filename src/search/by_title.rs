@@ -1,16 +1,20 @@
 use std::collections::HashMap;
-use std::str::FromStr;
+use std::fmt;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
 
 use hyper::Uri;
 use scraper::{ElementRef, Html};
 
+use crate::error::{ImdbError, ParseError};
 use crate::helpers::helper_tags::ATag;
 use crate::helpers::{
-    element_parser::ElementParserHelper, get_selector, html_parser::HtmlParserHelper,
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper,
 };
 use crate::ImdbSearchEngine;
 
-use super::By;
+use super::filters::{Certificate, Date, Genre, SortOrder, Span, TitleFilters, TitleType};
+use super::pagination::PageInfo;
+use super::{parse_uri, By, Paginated};
 
 #[derive(Debug, Clone)]
 pub struct PeopleInfo {
@@ -112,7 +116,7 @@ impl TitleSearchItem {
                 group
                     .1
                     .iter()
-                    .map(|p| people_to_str(p))
+                    .map(&people_to_str)
                     .collect::<Vec<String>>()
                     .join(people_separator),
             ));
@@ -122,9 +126,10 @@ impl TitleSearchItem {
     }
 }
 
-impl ToString for TitleSearchItem {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for TitleSearchItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "{} {}\n- {}\n- Rating: {}\n- {}",
             self.title.text(),
             self.years,
@@ -137,22 +142,113 @@ impl ToString for TitleSearchItem {
 
 pub struct TitleSearch {
     items: Vec<TitleSearchItem>,
+    warnings: Vec<ParseError>,
+    page: PageInfo,
+    query: ByTitle,
 }
 
 impl TitleSearch {
     pub fn items(&self) -> &[TitleSearchItem] {
         self.items.as_ref()
     }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
+
+    /// All matches across every page, from the "1-50 of N titles." header.
+    pub fn total_results(&self) -> Option<u32> {
+        self.page.total_results
+    }
+
+    /// Positions of this page's items among all matches, starting at `1`.
+    pub fn range(&self) -> Option<RangeInclusive<u32>> {
+        self.page.range.clone()
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.page.next_start.is_some()
+    }
+
+    /// The `ByTitle` for the following page, keeping every other option.
+    pub fn next_query(&self) -> Option<ByTitle> {
+        Some(ByTitle {
+            start: self.page.next_start?,
+            ..self.query.clone()
+        })
+    }
 }
 
+#[derive(Debug, Clone)]
 pub struct ByTitle {
     pub(crate) start: u16,
     pub(crate) count: u8,
+    pub(crate) lenient: bool,
+    pub(crate) filters: TitleFilters,
 }
 
 impl ByTitle {
     pub fn new(start: u16, count: u8) -> Self {
-        Self { start, count }
+        Self {
+            start,
+            count,
+            lenient: false,
+            filters: TitleFilters::default(),
+        }
+    }
+
+    /// Starts a search with advanced filters, from the first page of 10 items.
+    pub fn builder() -> ByTitleBuilder {
+        ByTitleBuilder::default()
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    fn parse_item(
+        element: ElementRef,
+        contents: ElementRef,
+    ) -> Result<TitleSearchItem, ParseError> {
+        let img_ele = element.require_first("div.lister-item-image>a>img")?;
+        let name_ele = contents.require_first("h3.lister-item-header>a")?;
+        let year_ele = contents.require_first("h3.lister-item-header>span.lister-item-year")?;
+
+        let p_eles = contents.select_all("p");
+        let nth_p = |n: usize| {
+            p_eles
+                .get(n)
+                .copied()
+                .ok_or_else(|| ParseError::new(format!("p:nth-of-type({})", n + 1), &contents))
+        };
+        let info_ele = nth_p(0)?;
+        let summery_ele = nth_p(1)?;
+        let peoples = nth_p(2)?; // always exists ...
+
+        let rating = match contents.select_first("div.ratings-bar>div.ratings-imdb-rating") {
+            Some(ele) => ele.require_attr("data-value")?.to_string(),
+            None => "No rating.".to_string(),
+        };
+        let image_url = img_ele.require_attr("src")?.to_string();
+
+        let info_spans = info_ele
+            .select_all("span")
+            .iter()
+            .map(|e| e.inner_html().trim().to_string())
+            .collect::<Vec<String>>();
+
+        let info_spans_str = info_spans.join(" ");
+
+        Ok(TitleSearchItem {
+            title: name_ele.require_a_tag()?,
+            image_url,
+            years: year_ele.inner_html(),
+            info: info_spans_str,
+            rating,
+            summery: summery_ele.inner_html().trim().to_string(),
+            peoples_info: ByTitle::parse_people_tag(peoples),
+        })
     }
 
     fn parse_people_tag(ele: ElementRef) -> HashMap<String, Vec<PeopleInfo>> {
@@ -174,8 +270,9 @@ impl ByTitle {
                     continue;
                 }
 
-                let val = people_roles.get_mut(&current_role).unwrap();
-                val.push(item)
+                if let Some(val) = people_roles.get_mut(&current_role) {
+                    val.push(item)
+                }
             }
         }
 
@@ -196,16 +293,13 @@ impl ByTitle {
         for (role, names) in people_roles {
             let mut names_in_role = vec![];
             for name in names {
-                match peoples_link.remove(&name) {
-                    Some(link) => {
-                        names_in_role.push(PeopleInfo {
-                            name,
-                            link,
-                            role: role.clone(),
-                        });
-                    }
-                    None => (),
-                };
+                if let Some(link) = peoples_link.remove(&name) {
+                    names_in_role.push(PeopleInfo {
+                        name,
+                        link,
+                        role: role.clone(),
+                    });
+                }
             }
 
             people_info.insert(role, names_in_role);
@@ -220,6 +314,117 @@ impl Default for ByTitle {
         Self {
             start: 1,
             count: 10,
+            lenient: false,
+            filters: TitleFilters::default(),
+        }
+    }
+}
+
+/// Builds a [`ByTitle`] with the advanced search filters of `/search/title/`.
+///
+/// Any range works, `7.0..`, `..=120` or `10..100` (votes up to 99). Invalid
+/// values are reported by [`ByTitleBuilder::build`].
+#[derive(Debug, Default)]
+pub struct ByTitleBuilder {
+    by: ByTitle,
+    error: Option<ImdbError>,
+}
+
+impl ByTitleBuilder {
+    fn check(mut self, result: Result<(), ImdbError>) -> Self {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+        self
+    }
+
+    pub fn start(mut self, start: u16) -> Self {
+        self.by.start = start;
+        self
+    }
+
+    pub fn count(mut self, count: u8) -> Self {
+        self.by.count = count;
+        self
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.by.lenient = lenient;
+        self
+    }
+
+    pub fn genres(mut self, genres: impl IntoIterator<Item = Genre>) -> Self {
+        self.by.filters.genres.extend(genres);
+        self
+    }
+
+    pub fn title_types(mut self, title_types: impl IntoIterator<Item = TitleType>) -> Self {
+        self.by.filters.title_types.extend(title_types);
+        self
+    }
+
+    pub fn release_date(mut self, range: impl RangeBounds<Date>) -> Self {
+        let span = Span::new("release_date", range);
+        let result = span.map(|span| self.by.filters.release_date = Some(span));
+        self.check(result)
+    }
+
+    /// Between `1.0` and `10.0`, exclusive bounds step by a tenth.
+    pub fn user_rating(mut self, range: impl RangeBounds<f32>) -> Self {
+        let finite = |bound: Bound<&f32>| match bound {
+            Bound::Included(value) | Bound::Excluded(value) => value.is_finite(),
+            Bound::Unbounded => true,
+        };
+        if !finite(range.start_bound()) || !finite(range.end_bound()) {
+            let error = ImdbError::InvalidQuery("user_rating must be a number".to_string());
+            return self.check(Err(error));
+        }
+
+        let span = Span::new("user_rating", range).and_then(|s| s.within("user_rating", 1.0, 10.0));
+        let result = span.map(|span| self.by.filters.user_rating = Some(span));
+        self.check(result)
+    }
+
+    pub fn num_votes(mut self, range: impl RangeBounds<u32>) -> Self {
+        let span = Span::new("num_votes", range);
+        let result = span.map(|span| self.by.filters.num_votes = Some(span));
+        self.check(result)
+    }
+
+    /// ISO 3166 alpha-2 codes, like `us` or `gb`.
+    pub fn countries<'a>(mut self, codes: impl IntoIterator<Item = &'a str>) -> Self {
+        let result = self.by.filters.add_countries(codes);
+        self.check(result)
+    }
+
+    /// ISO 639 codes, like `en` or `fr`.
+    pub fn languages<'a>(mut self, codes: impl IntoIterator<Item = &'a str>) -> Self {
+        let result = self.by.filters.add_languages(codes);
+        self.check(result)
+    }
+
+    pub fn certificates(mut self, certificates: impl IntoIterator<Item = Certificate>) -> Self {
+        self.by.filters.certificates.extend(certificates);
+        self
+    }
+
+    /// In minutes.
+    pub fn runtime(mut self, range: impl RangeBounds<u32>) -> Self {
+        let span = Span::new("runtime", range);
+        let result = span.map(|span| self.by.filters.runtime = Some(span));
+        self.check(result)
+    }
+
+    pub fn sort(mut self, sort: SortOrder) -> Self {
+        self.by.filters.sort = Some(sort);
+        self
+    }
+
+    /// The first invalid value given to the builder, if any, as an [`ImdbError::InvalidQuery`].
+    pub fn build(self) -> Result<ByTitle, ImdbError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.by),
         }
     }
 }
@@ -227,69 +432,47 @@ impl Default for ByTitle {
 impl By for ByTitle {
     type ParseResult = TitleSearch;
 
-    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Uri {
-        Uri::from_str(
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(
             format!(
-                "{}/search/title/?title={}&start={}&count={}",
+                "{}/search/title/?title={}{}&start={}&count={}",
                 engine.base_uri(),
                 query,
+                self.filters.to_query_string(),
                 self.start,
                 self.count
             )
             .as_str(),
         )
-        .unwrap()
-    }
-
-    fn parse_result(&self, html: Html) -> Self::ParseResult {
-        let item_list_selector = get_selector("div.lister-list>div");
-        let item_content_p_selector = get_selector("p");
-        let spans_selector = get_selector("span");
-        let item_content_rating_bar_selector =
-            get_selector("div.ratings-bar>div.ratings-imdb-rating");
-
-        let mut items = vec![];
-
-        for element in html.select(&item_list_selector) {
-            let contents = element.select_first("div.lister-item-content");
-            if let Some(contents) = contents {
-                let img_ele = element.select_first("div.lister-item-image>a>img").unwrap();
-                let name_ele = contents.select_first("h3.lister-item-header>a").unwrap();
-                let year_ele = contents
-                    .select_first("h3.lister-item-header>span.lister-item-year")
-                    .unwrap();
-
-                let mut p_text_muted_eles = contents.select(&item_content_p_selector);
-                let info_ele = p_text_muted_eles.next().unwrap();
-                let summery_ele = p_text_muted_eles.next().unwrap();
-                let rating = match contents.select(&item_content_rating_bar_selector).next() {
-                    Some(ele) => ele.value().attr("data-value").unwrap(),
-                    None => "No rating.",
-                }
-                .to_string();
-                let image_url = img_ele.value().attr("src").unwrap().to_string();
-                let peoples = p_text_muted_eles.next().unwrap(); // always exists ...
-
-                let info_spans = info_ele
-                    .select(&spans_selector)
-                    .map(|e| e.inner_html().trim().to_string())
-                    .collect::<Vec<String>>();
-
-                let info_spans_str = info_spans.join(" ");
-
-                items.push(TitleSearchItem {
-                    title: name_ele.parse_a_tag().unwrap(),
-                    image_url,
-                    years: year_ele.inner_html(),
-                    info: info_spans_str,
-                    rating,
-                    summery: summery_ele.inner_html().trim().to_string(),
-                    peoples_info: ByTitle::parse_people_tag(peoples),
-                });
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let mut results = vec![];
+        for element in html.select_all("div.lister-list>div") {
+            if let Some(contents) = element.select_first("div.lister-item-content") {
+                results.push(ByTitle::parse_item(element, contents));
             }
         }
 
-        TitleSearch { items }
+        let (items, warnings) = collect_items(self.lenient, results)?;
+        Ok(TitleSearch {
+            items,
+            warnings,
+            page: PageInfo::parse(&html, self.start),
+            query: self.clone(),
+        })
+    }
+}
+
+impl Paginated for ByTitle {
+    type Item = TitleSearchItem;
+
+    fn next_page(&self, result: &TitleSearch) -> Option<Self> {
+        result.next_query()
+    }
+
+    fn into_items(result: TitleSearch) -> Vec<TitleSearchItem> {
+        result.items
     }
 }
 
@@ -297,8 +480,106 @@ impl By for ByTitle {
 pub mod test {
     use scraper::Html;
 
+    use crate::search::By;
+
     use super::ByTitle;
 
+    const BROKEN_AND_VALID_ITEMS: &str = r#"
+<div class="lister-list">
+    <div class="lister-item mode-advanced">
+        <div class="lister-item-image float-left">
+            <a href="/title/tt0080684/"><img src="https://m.media-amazon.com/images/M/empire.jpg"></a>
+        </div>
+        <div class="lister-item-content">
+            <h3 class="lister-item-header"><a href="/title/tt0080684/">The Empire Strikes Back</a></h3>
+            <p class="text-muted"><span class="runtime">124 min</span></p>
+            <p class="text-muted">After the Rebels are overpowered by the Empire.</p>
+            <p class="">Director: <a href="/name/nm0449984/">Irvin Kershner</a></p>
+        </div>
+    </div>
+    <div class="lister-item mode-advanced">
+        <div class="lister-item-image float-left">
+            <a href="/title/tt0086190/"><img src="https://m.media-amazon.com/images/M/jedi.jpg"></a>
+        </div>
+        <div class="lister-item-content">
+            <h3 class="lister-item-header">
+                <a href="/title/tt0086190/">Return of the Jedi</a>
+                <span class="lister-item-year text-muted unbold">(1983)</span>
+            </h3>
+            <p class="text-muted"><span class="runtime">131 min</span></p>
+            <p class="text-muted">After rescuing Han Solo from Jabba the Hutt.</p>
+            <p class="">Director: <a href="/name/nm0549658/">Richard Marquand</a></p>
+        </div>
+    </div>
+</div>
+"#;
+
+    #[test]
+    fn test_pagination_metadata() {
+        let html = Html::parse_document(&BROKEN_AND_VALID_ITEMS.replace(
+            r#"<div class="lister-list">"#,
+            r#"<div class="desc">
+                <span>51-100 of 3,385 titles.</span>
+                <a href="/search/title/?title=star&start=101&ref_=adv_nxt" class="lister-page-next next-page">Next »</a>
+            </div>
+            <div class="lister-list">"#,
+        ));
+        let result = ByTitle::new(51, 50)
+            .lenient(true)
+            .parse_result(html)
+            .unwrap();
+
+        assert_eq!(result.total_results(), Some(3385));
+        assert_eq!(result.range(), Some(51..=100));
+        assert!(result.has_next());
+        let next = result.next_query().unwrap();
+        assert_eq!(next.start, 101);
+        assert_eq!(next.count, 50);
+        assert!(next.lenient);
+
+        let last_page = Html::parse_document(&BROKEN_AND_VALID_ITEMS.replace(
+            r#"<div class="lister-list">"#,
+            r#"<div class="desc"><span>2 titles.</span></div><div class="lister-list">"#,
+        ));
+        let result = ByTitle::default()
+            .lenient(true)
+            .parse_result(last_page)
+            .unwrap();
+        assert_eq!(result.total_results(), Some(2));
+        assert_eq!(result.range(), Some(1..=2));
+        assert!(!result.has_next());
+        assert!(result.next_query().is_none());
+    }
+
+    #[test]
+    fn test_strict_parse_names_failing_selector() {
+        let error = ByTitle::default()
+            .parse_result(Html::parse_document(BROKEN_AND_VALID_ITEMS))
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.selector(),
+            "h3.lister-item-header>span.lister-item-year"
+        );
+        assert!(error
+            .snippet()
+            .starts_with("<div class=\"lister-item-content\">"));
+    }
+
+    #[test]
+    fn test_lenient_parse_skips_malformed_items() {
+        let result = ByTitle::default()
+            .lenient(true)
+            .parse_result(Html::parse_document(BROKEN_AND_VALID_ITEMS))
+            .unwrap();
+
+        assert_eq!(result.items().len(), 1);
+        assert_eq!(result.items()[0].title().text(), "Return of the Jedi");
+        assert_eq!(result.items()[0].rating(), "No rating.");
+        assert_eq!(result.warnings().len(), 1);
+    }
+
     #[test]
     fn test_parse_people() {
         ByTitle::parse_people_tag(
@@ -1,18 +1,121 @@
-use std::str::FromStr;
-
 use hyper::Uri;
+use scraper::{ElementRef, Html};
 
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
 use crate::helpers::{
-    element_parser::ElementParserHelper, get_selector, html_parser::HtmlParserHelper,
+    collect_items, element_parser::ElementParserHelper, html_parser::HtmlParserHelper, link_id,
+    parenthesised,
 };
 
+use super::find::{find_rows, find_uri, FindRow};
 use super::By;
 
-pub struct ByTitleFind;
+pub struct ByTitleFind {
+    lenient: bool,
+}
 
+// Rows without an image or a link were always skipped, so this stays lenient
+// unless asked otherwise.
 impl Default for ByTitleFind {
     fn default() -> Self {
-        Self {}
+        Self { lenient: true }
+    }
+}
+
+impl ByTitleFind {
+    /// `false` fails on the first malformed row.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub(crate) fn parse_row(tr: ElementRef) -> Result<ByTitleFoundItem, ParseError> {
+        let row = FindRow::parse(tr)?;
+        let a_tag = row.main_link()?;
+
+        // <a href="/title/tt10763556/">...</a> (2015) (TV Episode)
+        let mut year = None;
+        let mut kind = None;
+        for group in parenthesised(&row.loose_text()) {
+            if group.len() == 4 && group.chars().all(|c| c.is_ascii_digit()) {
+                year = year.or_else(|| group.parse().ok());
+            } else {
+                // Anything else is a roman numeral telling apart titles of the
+                // same name and year, or a note like `(in development)`.
+                kind = kind.or_else(|| TitleKind::from_label(&group));
+            }
+        }
+
+        // <small>- Season 1 <span class="ghost">|</span> Episode 13 </small>
+        // <small>- <a href="/title/tt10763450/">Blind Wave Movie Reviews</a> (2015) (TV Series)</small>
+        let mut season = None;
+        let mut episode = None;
+        let mut series = None;
+        for small in row.text.select_all("small") {
+            if let Some(a) = small.select_first("a") {
+                series = series.or_else(|| {
+                    Some(ATag {
+                        text: a.text_content(),
+                        link: a.value().attr("href")?.to_string(),
+                    })
+                });
+                continue;
+            }
+            let text = small.text_content();
+            let words = text.split_whitespace().collect::<Vec<&str>>();
+            for pair in words.windows(2) {
+                match pair[0] {
+                    "Season" => season = pair[1].parse().ok(),
+                    "Episode" => episode = pair[1].parse().ok(),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(ByTitleFoundItem {
+            title: a_tag.text,
+            link: a_tag.link,
+            img_uri: row.image_src()?,
+            year,
+            kind: kind.unwrap_or(TitleKind::Movie),
+            season,
+            episode,
+            series,
+        })
+    }
+}
+
+/// What a title is, from the `(TV Series)` like label of find results.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TitleKind {
+    /// Find results do not label movies.
+    Movie,
+    TvSeries,
+    TvMiniSeries,
+    TvEpisode,
+    TvMovie,
+    TvSpecial,
+    TvShort,
+    Short,
+    Video,
+    VideoGame,
+}
+
+impl TitleKind {
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "TV Series" => Some(Self::TvSeries),
+            "TV Mini Series" | "TV Mini-Series" => Some(Self::TvMiniSeries),
+            "TV Episode" => Some(Self::TvEpisode),
+            "TV Movie" => Some(Self::TvMovie),
+            "TV Special" => Some(Self::TvSpecial),
+            "TV Short" => Some(Self::TvShort),
+            "Short" => Some(Self::Short),
+            "Video" => Some(Self::Video),
+            "Video Game" => Some(Self::VideoGame),
+            _ => None,
+        }
     }
 }
 
@@ -20,11 +123,16 @@ pub struct ByTitleFoundItem {
     title: String,
     link: String,
     img_uri: String,
+    year: Option<u16>,
+    kind: TitleKind,
+    season: Option<u16>,
+    episode: Option<u16>,
+    series: Option<ATag>,
 }
 
 impl ByTitleFoundItem {
-    pub fn get_title_id(&self) -> String {
-        self.link.split("/").nth(2).unwrap().to_string()
+    pub fn get_title_id(&self) -> Option<String> {
+        link_id(&self.link)
     }
 
     pub fn title(&self) -> &str {
@@ -38,84 +146,65 @@ impl ByTitleFoundItem {
     pub fn img_uri(&self) -> &str {
         self.img_uri.as_ref()
     }
+
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    pub fn kind(&self) -> &TitleKind {
+        &self.kind
+    }
+
+    /// Only set for episodes, and not always for them.
+    pub fn season(&self) -> Option<u16> {
+        self.season
+    }
+
+    pub fn episode(&self) -> Option<u16> {
+        self.episode
+    }
+
+    /// The series an episode belongs to.
+    pub fn series(&self) -> Option<&ATag> {
+        self.series.as_ref()
+    }
+
+    pub fn get_series_id(&self) -> Option<String> {
+        link_id(&self.series.as_ref()?.link)
+    }
 }
 
 pub struct ByTitleFound {
     items: Vec<ByTitleFoundItem>,
+    warnings: Vec<ParseError>,
 }
 
 impl ByTitleFound {
     pub fn items(&self) -> &[ByTitleFoundItem] {
         self.items.as_ref()
     }
+
+    pub fn warnings(&self) -> &[ParseError] {
+        self.warnings.as_ref()
+    }
 }
 
 impl By for ByTitleFind {
     type ParseResult = ByTitleFound;
 
-    fn get_uri(&self, engine: &crate::prelude::ImdbSearchEngine, query: &str) -> hyper::Uri {
-        Uri::from_str(format!("{}/find?s=tt&q={}", engine.base_uri(), query).as_str()).unwrap()
+    fn get_uri(
+        &self,
+        engine: &crate::prelude::ImdbSearchEngine,
+        query: &str,
+    ) -> Result<Uri, ImdbError> {
+        find_uri(engine, "tt", query)
     }
 
-    fn parse_result(&self, html: scraper::Html) -> Self::ParseResult {
-        let items_selector = get_selector("#main > div > div.findSection > table > tbody > tr");
-        let td_selector = get_selector("td");
-
-        let mut items = vec![];
-        for tr in html.select(&items_selector) {
-            // We have tr here ...
-            // Every tr has exactly 2 tds.
-            // First > Photo
-            // Second > Text
-
-            let mut tds = tr.select(&td_selector);
-
-            let first_ele = match tds.next() {
-                Some(f) => f,
-                None => {
-                    // What ???
-                    continue;
-                }
-            };
-
-            let second_ele = match tds.next() {
-                Some(s) => s,
-                None => {
-                    // Whaaaat ???
-                    continue;
-                }
-            };
-
-            let img_ele = match first_ele.select_first("a > img") {
-                Some(img) => img,
-                None => continue,
-            };
-
-            let image_src = if let Some(src) = img_ele.value().attr("src") {
-                src
-            } else {
-                continue;
-            };
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let rows = find_rows(&html).into_iter().map(ByTitleFind::parse_row);
 
-            let text_a_ele = if let Some(ele) = second_ele.select_first("a") {
-                ele
-            } else {
-                continue;
-            };
-
-            let a_tag = match text_a_ele.parse_a_tag() {
-                Some(a) => a,
-                None => continue,
-            };
-
-            items.push(ByTitleFoundItem {
-                title: a_tag.text,
-                link: a_tag.link,
-                img_uri: image_src.to_string(),
-            })
-        }
-
-        ByTitleFound { items }
+        let (items, warnings) = collect_items(self.lenient, rows)?;
+        Ok(ByTitleFound { items, warnings })
     }
 }
 
@@ -125,7 +214,7 @@ pub mod tests {
 
     use crate::search::By;
 
-    use super::ByTitleFind;
+    use super::{ByTitleFind, TitleKind};
 
     #[test]
     pub fn test_by_title_parser() {
@@ -235,6 +324,27 @@ pub mod tests {
                         <td class="result_text"> <a href="/title/tt13622982/?ref_=fn_tt_tt_12">Star Wars: Visions</a>
                             (2021) (TV Series) </td>
                     </tr>
+                    <tr class="findResult odd">
+                        <td class="primary_photo"> <a href="/title/tt13622958/?ref_=fn_tt_tt_13"><img
+                                    src="https://m.media-amazon.com/images/M/lando.jpg"></a>
+                        </td>
+                        <td class="result_text"> <a href="/title/tt13622958/?ref_=fn_tt_tt_13">Lando</a>
+                            (2019) (TV Series) (in development) </td>
+                    </tr>
+                    <tr class="findResult even">
+                        <td class="primary_photo"> <a href="/title/tt11813216/?ref_=fn_tt_tt_14"><img
+                                    src="https://m.media-amazon.com/images/M/rogue_squadron.jpg"></a>
+                        </td>
+                        <td class="result_text"> <a href="/title/tt11813216/?ref_=fn_tt_tt_14">Rogue Squadron</a>
+                            (2023) (in development) </td>
+                    </tr>
+                    <tr class="findResult odd">
+                        <td class="primary_photo"> <a href="/title/tt0076759/?ref_=fn_tt_tt_15"><img
+                                    src="https://m.media-amazon.com/images/M/star_wars.jpg"></a>
+                        </td>
+                        <td class="result_text"> <a href="/title/tt0076759/?ref_=fn_tt_tt_15">Star Wars</a>
+                            (1977) <br/>aka <i>"Star Wars: Episode IV - A New Hope"</i> </td>
+                    </tr>
                 </tbody>
             </table>
         </div>
@@ -244,11 +354,76 @@ pub mod tests {
         );
 
         let by_title_find = ByTitleFind::default();
-        let result = by_title_find.parse_result(html);
+        let result = by_title_find.parse_result(html).unwrap();
 
         let first = result.items.first().unwrap();
-        let title_id = first.get_title_id();
+        assert_eq!(first.get_title_id().as_deref(), Some("tt9336300"));
+        assert_eq!(first.year(), Some(2018));
+        assert_eq!(first.kind(), &TitleKind::Movie);
+
+        let episode = &result.items()[2];
+        assert_eq!(episode.year(), Some(2015));
+        assert_eq!(episode.kind(), &TitleKind::TvEpisode);
+        assert_eq!((episode.season(), episode.episode()), (Some(1), Some(13)));
+        assert_eq!(episode.series().unwrap().text(), "Blind Wave Movie Reviews");
+        assert_eq!(episode.get_series_id().as_deref(), Some("tt10763450"));
+
+        // An episode without season and episode numbers.
+        let review = &result.items()[3];
+        assert_eq!(review.season(), None);
+        assert_eq!(review.get_series_id().as_deref(), Some("tt11953086"));
+
+        assert_eq!(result.items()[1].kind(), &TitleKind::TvSeries);
+        // Only the first label is the kind.
+        assert_eq!(result.items()[12].kind(), &TitleKind::TvSeries);
+        // Notes and aka lines are not kinds.
+        assert_eq!(result.items()[13].kind(), &TitleKind::Movie);
+        assert_eq!(result.items()[14].kind(), &TitleKind::Movie);
+        assert_eq!(result.items()[14].year(), Some(1977));
+        assert!(result.items()[1].series().is_none());
+    }
 
-        println!("{}", title_id);
+    #[test]
+    pub fn test_rows_without_image_are_skipped_by_default() {
+        let page = r#"
+<div id="main"><div class="article"><div class="findSection"><table class="findList"><tbody>
+    <tr class="findResult odd">
+        <td class="primary_photo"> <a href="/title/tt0076759/"><img src="https://m.media-amazon.com/images/M/star_wars.jpg"></a> </td>
+        <td class="result_text"> <a href="/title/tt0076759/">Star Wars</a> (1977) </td>
+    </tr>
+    <tr class="findResult even">
+        <td class="primary_photo"> <a href="/title/tt0080684/"></a> </td>
+        <td class="result_text"> <a href="/title/tt0080684/">The Empire Strikes Back</a> (1980) </td>
+    </tr>
+</tbody></table></div></div></div>
+"#;
+
+        let result = ByTitleFind::default()
+            .parse_result(Html::parse_fragment(page))
+            .unwrap();
+        assert_eq!(result.items().len(), 1);
+        assert_eq!(result.warnings().len(), 1);
+
+        assert!(ByTitleFind::default()
+            .lenient(false)
+            .parse_result(Html::parse_fragment(page))
+            .is_err());
+    }
+
+    #[test]
+    pub fn test_odd_links_have_no_id() {
+        let page = r#"
+<div id="main"><div class="article"><div class="findSection"><table class="findList"><tbody>
+    <tr class="findResult odd">
+        <td class="primary_photo"> <a href="tt0076759"><img src="https://m.media-amazon.com/images/M/star_wars.jpg"></a> </td>
+        <td class="result_text"> <a href="tt0076759">Star Wars</a> (1977) </td>
+    </tr>
+</tbody></table></div></div></div>
+"#;
+
+        let result = ByTitleFind::default()
+            .parse_result(Html::parse_fragment(page))
+            .unwrap();
+        assert_eq!(result.items()[0].get_title_id(), None);
     }
 }
@@ -0,0 +1,532 @@
+use hyper::Uri;
+use scraper::Html;
+use serde_json::Value;
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::json::{json_list, json_number, json_str, ld_json, next_data};
+use crate::helpers::{element_parser::ElementParserHelper, html_parser::HtmlParserHelper};
+use crate::ImdbSearchEngine;
+
+use super::filters::{imdb_id, Date};
+use super::{parse_uri, By};
+
+/// Loads a title page, `/title/{id}/`, the query being the id like `tt0076759`.
+///
+/// Fields come from the JSON-LD block first, then from `__NEXT_DATA__`, then
+/// from the rendered page.
+///
+/// There is no country option: the title page only carries the title for
+/// the language it is served in, the titles of other countries are on the
+/// release info page. [`ImdbSearchEngine::localised_title`] loads that one.
+#[derive(Debug, Clone, Default)]
+pub struct ByTitleId;
+
+impl ByTitleId {
+    fn from_ld_json(ld: &Value) -> TitleDetails {
+        let people = |key: &str| {
+            json_list(ld.get(key))
+                .into_iter()
+                .filter(|p| p.get("@type").and_then(Value::as_str) == Some("Person"))
+                .filter_map(|p| {
+                    Some(ATag {
+                        text: json_str(p.get("name")?)?,
+                        link: json_str(p.get("url")?)?,
+                    })
+                })
+                .collect::<Vec<ATag>>()
+        };
+        let rating = ld.get("aggregateRating");
+        let release_date = ld.get("datePublished").and_then(json_str);
+
+        TitleDetails {
+            id: ld
+                .get("url")
+                .and_then(json_str)
+                .and_then(|u| id_from_link(&u)),
+            title: ld.get("name").and_then(json_str),
+            year: release_date.as_ref().and_then(|d| d.get(..4)?.parse().ok()),
+            runtime: ld
+                .get("duration")
+                .and_then(json_str)
+                .and_then(|d| parse_runtime(&d)),
+            genres: json_list(ld.get("genre"))
+                .into_iter()
+                .filter_map(json_str)
+                .collect(),
+            rating: rating
+                .and_then(|r| json_number(r.get("ratingValue")?))
+                .map(|r| r as f32),
+            votes: rating
+                .and_then(|r| json_number(r.get("ratingCount")?))
+                .map(|v| v as u32),
+            content_rating: ld.get("contentRating").and_then(json_str),
+            plot: ld.get("description").and_then(json_str),
+            poster: ld.get("image").and_then(json_str),
+            directors: people("director"),
+            // Creators are the writers, next to the companies behind the title.
+            writers: people("creator"),
+            cast: people("actor")
+                .into_iter()
+                .map(|a| CastMember {
+                    name: a.text,
+                    link: a.link,
+                    characters: vec![],
+                })
+                .collect(),
+            release_date: release_date.and_then(|d| Date::parse(&d)),
+            ..TitleDetails::default()
+        }
+    }
+
+    fn from_next_data(next: &Value) -> TitleDetails {
+        let above = next.pointer("/props/pageProps/aboveTheFoldData");
+        let main = next.pointer("/props/pageProps/mainColumnData");
+        let at = |path: &str| above.and_then(|a| a.pointer(path));
+        let texts = |list: Option<&Value>| {
+            json_list(list)
+                .into_iter()
+                .filter_map(|v| json_str(v.get("text")?))
+                .collect::<Vec<String>>()
+        };
+        let name = |name: &Value| {
+            Some(ATag {
+                text: json_str(name.pointer("/nameText/text")?)?,
+                link: format!("/name/{}/", json_str(name.get("id")?)?),
+            })
+        };
+        let credits = |category: &str| {
+            json_list(at("/principalCredits"))
+                .into_iter()
+                .filter(|c| c.pointer("/category/id").and_then(Value::as_str) == Some(category))
+                .flat_map(|c| json_list(c.get("credits")))
+                .filter_map(|c| name(c.get("name")?))
+                .collect::<Vec<ATag>>()
+        };
+        let release_date = at("/releaseDate").and_then(|d| {
+            let part = |key: &str| json_number(d.get(key)?);
+            Date::new(
+                part("year")? as u16,
+                part("month")? as u8,
+                part("day")? as u8,
+            )
+            .ok()
+        });
+
+        TitleDetails {
+            id: at("/id").and_then(json_str),
+            title: at("/titleText/text").and_then(json_str),
+            original_title: at("/originalTitleText/text").and_then(json_str),
+            year: at("/releaseYear/year")
+                .and_then(json_number)
+                .map(|y| y as u16),
+            runtime: at("/runtime/seconds")
+                .and_then(json_number)
+                .map(|s| s as u32 / 60),
+            genres: texts(at("/genres/genres")),
+            rating: at("/ratingsSummary/aggregateRating")
+                .and_then(json_number)
+                .map(|r| r as f32),
+            votes: at("/ratingsSummary/voteCount")
+                .and_then(json_number)
+                .map(|v| v as u32),
+            content_rating: at("/certificate/rating").and_then(json_str),
+            plot: at("/plot/plotText/plainText").and_then(json_str),
+            poster: at("/primaryImage/url").and_then(json_str),
+            directors: credits("director"),
+            writers: credits("writer"),
+            cast: json_list(main.and_then(|m| m.pointer("/cast/edges")))
+                .into_iter()
+                .filter_map(|edge| {
+                    let node = edge.get("node")?;
+                    let actor = name(node.get("name")?)?;
+                    Some(CastMember {
+                        name: actor.text,
+                        link: actor.link,
+                        characters: json_list(node.get("characters"))
+                            .into_iter()
+                            .filter_map(|c| json_str(c.get("name")?))
+                            .collect(),
+                    })
+                })
+                .collect(),
+            countries: texts(main.and_then(|m| m.pointer("/countriesOfOrigin/countries"))),
+            languages: texts(main.and_then(|m| m.pointer("/spokenLanguages/spokenLanguages"))),
+            release_date,
+        }
+    }
+
+    fn from_dom(html: &Html) -> TitleDetails {
+        let text = |selector: &str| {
+            html.select_first(selector)
+                .map(|e| e.text_content())
+                .filter(|t| !t.is_empty())
+        };
+        let texts = |selector: &str| {
+            html.select_all(selector)
+                .iter()
+                .map(|e| e.text_content())
+                .filter(|t| !t.is_empty())
+                .collect::<Vec<String>>()
+        };
+        // <li data-testid="title-pc-principal-credit"><span>Director</span><ul><li><a href="/name/...">...</a></li></ul></li>
+        let credits = |label: &str| {
+            html.select_all(r#"li[data-testid="title-pc-principal-credit"]"#)
+                .into_iter()
+                .filter(|li| {
+                    li.select_first("span, a")
+                        .is_some_and(|l| l.text_content().starts_with(label))
+                })
+                .flat_map(|li| {
+                    li.select_all("ul a")
+                        .iter()
+                        .filter_map(|a| {
+                            Some(ATag {
+                                text: a.text_content(),
+                                link: a.value().attr("href")?.to_string(),
+                            })
+                        })
+                        .collect::<Vec<ATag>>()
+                })
+                .collect::<Vec<ATag>>()
+        };
+
+        TitleDetails {
+            id: html
+                .select_first(r#"link[rel="canonical"]"#)
+                .and_then(|l| id_from_link(l.value().attr("href")?)),
+            title: text(r#"h1[data-testid="hero__pageTitle"]"#),
+            original_title: text(r#"div[data-testid="hero-title-block__original-title"]"#)
+                .map(|t| t.trim_start_matches("Original title:").trim().to_string()),
+            year: text(r#"h1[data-testid="hero__pageTitle"] ~ ul a[href*="releaseinfo"]"#)
+                .and_then(|y| y.get(..4)?.parse().ok()),
+            runtime: text(r#"li[data-testid="title-techspec_runtime"] div"#)
+                .and_then(|r| parse_runtime(&r)),
+            genres: texts(r#"div[data-testid="genres"] a"#),
+            rating: text(r#"div[data-testid="hero-rating-bar__aggregate-rating__score"] > span"#)
+                .and_then(|r| r.parse().ok()),
+            plot: text(r#"span[data-testid="plot-xl"]"#),
+            poster: html
+                .select_first(r#"div[data-testid="hero-media__poster"] img"#)
+                .and_then(|img| img.value().attr("src"))
+                .map(|src| src.to_string()),
+            directors: credits("Director"),
+            writers: credits("Writer"),
+            cast: html
+                .select_all(r#"div[data-testid="title-cast-item"]"#)
+                .iter()
+                .filter_map(|item| {
+                    let actor = item.select_first(r#"a[data-testid="title-cast-item__actor"]"#)?;
+                    Some(CastMember {
+                        name: actor.text_content(),
+                        link: actor.value().attr("href")?.to_string(),
+                        characters: item
+                            .select_all(r#"a[data-testid="cast-item-characters-link"] span"#)
+                            .iter()
+                            .map(|c| c.text_content())
+                            .collect(),
+                    })
+                })
+                .collect(),
+            countries: texts(r#"li[data-testid="title-details-origin"] ul a"#),
+            languages: texts(r#"li[data-testid="title-details-languages"] ul a"#),
+            ..TitleDetails::default()
+        }
+    }
+}
+
+/// Minutes in `PT2H1M`, `2h 1m` or `2 hours 1 minute`.
+fn parse_runtime(text: &str) -> Option<u32> {
+    let mut minutes = None;
+    let mut number = None;
+    for c in text.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            number = Some(number.unwrap_or(0) * 10 + digit);
+        } else if c.is_alphabetic() {
+            if let Some(n) = number.take() {
+                match c.to_ascii_lowercase() {
+                    'h' => minutes = Some(minutes.unwrap_or(0) + n * 60),
+                    'm' => minutes = Some(minutes.unwrap_or(0) + n),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    minutes
+}
+
+fn id_from_link(link: &str) -> Option<String> {
+    link.split('/')
+        .skip_while(|part| *part != "title")
+        .nth(1)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+}
+
+pub struct CastMember {
+    name: String,
+    link: String,
+    characters: Vec<String>,
+}
+
+impl CastMember {
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn link(&self) -> &str {
+        self.link.as_ref()
+    }
+
+    /// Empty when the page does not say who they play.
+    pub fn characters(&self) -> &[String] {
+        self.characters.as_ref()
+    }
+}
+
+#[derive(Default)]
+pub struct TitleDetails {
+    id: Option<String>,
+    title: Option<String>,
+    original_title: Option<String>,
+    year: Option<u16>,
+    runtime: Option<u32>,
+    genres: Vec<String>,
+    rating: Option<f32>,
+    votes: Option<u32>,
+    content_rating: Option<String>,
+    plot: Option<String>,
+    poster: Option<String>,
+    directors: Vec<ATag>,
+    writers: Vec<ATag>,
+    cast: Vec<CastMember>,
+    countries: Vec<String>,
+    languages: Vec<String>,
+    release_date: Option<Date>,
+}
+
+impl TitleDetails {
+    /// Keeps every field already found, takes the missing ones from `fallback`.
+    fn or(self, fallback: Self) -> Self {
+        fn list<T>(found: Vec<T>, fallback: Vec<T>) -> Vec<T> {
+            if found.is_empty() {
+                fallback
+            } else {
+                found
+            }
+        }
+
+        Self {
+            id: self.id.or(fallback.id),
+            title: self.title.or(fallback.title),
+            original_title: self.original_title.or(fallback.original_title),
+            year: self.year.or(fallback.year),
+            runtime: self.runtime.or(fallback.runtime),
+            genres: list(self.genres, fallback.genres),
+            rating: self.rating.or(fallback.rating),
+            votes: self.votes.or(fallback.votes),
+            content_rating: self.content_rating.or(fallback.content_rating),
+            plot: self.plot.or(fallback.plot),
+            poster: self.poster.or(fallback.poster),
+            directors: list(self.directors, fallback.directors),
+            writers: list(self.writers, fallback.writers),
+            // JSON-LD lists the cast without the characters they play.
+            cast: if self.cast.iter().all(|c| c.characters.is_empty()) {
+                list(fallback.cast, self.cast)
+            } else {
+                self.cast
+            },
+            countries: list(self.countries, fallback.countries),
+            languages: list(self.languages, fallback.languages),
+            release_date: self.release_date.or(fallback.release_date),
+        }
+    }
+
+    /// Like `tt0076759`.
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or_default()
+    }
+
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or_default()
+    }
+
+    /// Only shown by IMDb when it differs from the title.
+    pub fn original_title(&self) -> Option<&str> {
+        self.original_title.as_deref()
+    }
+
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    /// In minutes.
+    pub fn runtime(&self) -> Option<u32> {
+        self.runtime
+    }
+
+    pub fn genres(&self) -> &[String] {
+        self.genres.as_ref()
+    }
+
+    pub fn rating(&self) -> Option<f32> {
+        self.rating
+    }
+
+    pub fn votes(&self) -> Option<u32> {
+        self.votes
+    }
+
+    /// Like `PG-13`.
+    pub fn content_rating(&self) -> Option<&str> {
+        self.content_rating.as_deref()
+    }
+
+    pub fn plot(&self) -> Option<&str> {
+        self.plot.as_deref()
+    }
+
+    pub fn poster(&self) -> Option<&str> {
+        self.poster.as_deref()
+    }
+
+    pub fn directors(&self) -> &[ATag] {
+        self.directors.as_ref()
+    }
+
+    pub fn writers(&self) -> &[ATag] {
+        self.writers.as_ref()
+    }
+
+    /// The top billed cast shown on the title page.
+    pub fn cast(&self) -> &[CastMember] {
+        self.cast.as_ref()
+    }
+
+    pub fn countries(&self) -> &[String] {
+        self.countries.as_ref()
+    }
+
+    pub fn languages(&self) -> &[String] {
+        self.languages.as_ref()
+    }
+
+    /// The first release.
+    pub fn release_date(&self) -> Option<Date> {
+        self.release_date
+    }
+}
+
+impl By for ByTitleId {
+    type ParseResult = TitleDetails;
+
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError> {
+        parse_uri(format!("{}/title/{}/", engine.base_uri(), imdb_id("tt", query)?).as_str())
+    }
+
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError> {
+        let mut details = TitleDetails::default();
+        if let Some(ld) = ld_json(&html) {
+            details = details.or(ByTitleId::from_ld_json(&ld));
+        }
+        if let Some(next) = next_data(&html) {
+            details = details.or(ByTitleId::from_next_data(&next));
+        }
+        details = details.or(ByTitleId::from_dom(&html));
+        // `__NEXT_DATA__` fills it in for every title, not only translated ones.
+        if details.original_title == details.title {
+            details.original_title = None;
+        }
+
+        if details.title.is_none() {
+            html.require_first(r#"h1[data-testid="hero__pageTitle"]"#)?;
+        }
+        if details.id.is_none() {
+            html.require_first(r#"link[rel="canonical"]"#)?;
+        }
+
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use scraper::Html;
+
+    use crate::search::filters::Date;
+    use crate::search::By;
+
+    use super::ByTitleId;
+
+    const TITLE_PAGE: &str = r#"
+<html>
+<head>
+    <link rel="canonical" href="https://www.imdb.com/title/tt0076759/">
+    <script type="application/ld+json">{"@context":"https://schema.org","@type":"Movie",
+        "url":"/title/tt0076759/","name":"Star Wars","image":"https://m.media-amazon.com/images/M/star_wars.jpg",
+        "aggregateRating":{"@type":"AggregateRating","ratingCount":1412345,"ratingValue":8.6},
+        "contentRating":"PG","genre":["Action","Adventure","Fantasy"],"datePublished":"1977-05-25",
+        "actor":[{"@type":"Person","url":"/name/nm0000434/","name":"Mark Hamill"}],
+        "director":{"@type":"Person","url":"/name/nm0000184/","name":"George Lucas"},
+        "creator":[{"@type":"Organization","url":"/company/co0071326/"},{"@type":"Person","url":"/name/nm0000184/","name":"George Lucas"}],
+        "duration":"PT2H1M"}</script>
+</head>
+<body>
+    <h1 data-testid="hero__pageTitle"><span>Star Wars: Episode IV - A New Hope</span></h1>
+    <span data-testid="plot-xl">Luke Skywalker joins forces with a Jedi Knight.</span>
+    <script id="__NEXT_DATA__" type="application/json">{"props":{"pageProps":{
+        "aboveTheFoldData":{"id":"tt0076759","titleText":{"text":"Star Wars"},
+            "originalTitleText":{"text":"Star Wars: Episode IV - A New Hope"}},
+        "mainColumnData":{
+            "cast":{"edges":[{"node":{"name":{"id":"nm0000434","nameText":{"text":"Mark Hamill"}},"characters":[{"name":"Luke Skywalker"}]}}]},
+            "countriesOfOrigin":{"countries":[{"id":"US","text":"United States"}]},
+            "spokenLanguages":{"spokenLanguages":[{"id":"en","text":"English"}]}}}}}</script>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn test_title_details_parser() {
+        let details = ByTitleId
+            .parse_result(Html::parse_document(TITLE_PAGE))
+            .unwrap();
+
+        assert_eq!(details.id(), "tt0076759");
+        assert_eq!(details.title(), "Star Wars");
+        assert_eq!(
+            details.original_title(),
+            Some("Star Wars: Episode IV - A New Hope")
+        );
+        assert_eq!(details.year(), Some(1977));
+        assert_eq!(details.runtime(), Some(121));
+        assert_eq!(details.genres(), ["Action", "Adventure", "Fantasy"]);
+        assert_eq!(details.rating(), Some(8.6));
+        assert_eq!(details.votes(), Some(1412345));
+        assert_eq!(details.content_rating(), Some("PG"));
+        assert_eq!(
+            details.plot(),
+            Some("Luke Skywalker joins forces with a Jedi Knight.")
+        );
+        assert_eq!(details.directors()[0].text(), "George Lucas");
+        assert_eq!(details.writers().len(), 1);
+        assert_eq!(details.cast()[0].name(), "Mark Hamill");
+        assert_eq!(details.cast()[0].characters(), ["Luke Skywalker"]);
+        assert_eq!(details.countries(), ["United States"]);
+        assert_eq!(details.languages(), ["English"]);
+        assert_eq!(
+            details.release_date(),
+            Some(Date::new(1977, 5, 25).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_original_title_only_when_it_differs() {
+        let page = TITLE_PAGE.replace(
+            r#""originalTitleText":{"text":"Star Wars: Episode IV - A New Hope"}"#,
+            r#""originalTitleText":{"text":"Star Wars"}"#,
+        );
+        let details = ByTitleId.parse_result(Html::parse_document(&page)).unwrap();
+        assert_eq!(details.original_title(), None);
+    }
+}
@@ -0,0 +1,764 @@
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+use urlencoding::encode;
+
+use crate::error::ImdbError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Genre {
+    Action,
+    Adventure,
+    Animation,
+    Biography,
+    Comedy,
+    Crime,
+    Documentary,
+    Drama,
+    Family,
+    Fantasy,
+    FilmNoir,
+    GameShow,
+    History,
+    Horror,
+    Music,
+    Musical,
+    Mystery,
+    News,
+    RealityTv,
+    Romance,
+    SciFi,
+    Sport,
+    TalkShow,
+    Thriller,
+    War,
+    Western,
+}
+
+impl Genre {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Action => "action",
+            Self::Adventure => "adventure",
+            Self::Animation => "animation",
+            Self::Biography => "biography",
+            Self::Comedy => "comedy",
+            Self::Crime => "crime",
+            Self::Documentary => "documentary",
+            Self::Drama => "drama",
+            Self::Family => "family",
+            Self::Fantasy => "fantasy",
+            Self::FilmNoir => "film-noir",
+            Self::GameShow => "game-show",
+            Self::History => "history",
+            Self::Horror => "horror",
+            Self::Music => "music",
+            Self::Musical => "musical",
+            Self::Mystery => "mystery",
+            Self::News => "news",
+            Self::RealityTv => "reality-tv",
+            Self::Romance => "romance",
+            Self::SciFi => "sci-fi",
+            Self::Sport => "sport",
+            Self::TalkShow => "talk-show",
+            Self::Thriller => "thriller",
+            Self::War => "war",
+            Self::Western => "western",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TitleType {
+    Feature,
+    TvMovie,
+    TvSeries,
+    TvEpisode,
+    TvSpecial,
+    TvMiniSeries,
+    Documentary,
+    VideoGame,
+    Short,
+    Video,
+    TvShort,
+}
+
+impl TitleType {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Feature => "feature",
+            Self::TvMovie => "tv_movie",
+            Self::TvSeries => "tv_series",
+            Self::TvEpisode => "tv_episode",
+            Self::TvSpecial => "tv_special",
+            Self::TvMiniSeries => "tv_miniseries",
+            Self::Documentary => "documentary",
+            Self::VideoGame => "video_game",
+            Self::Short => "short",
+            Self::Video => "video",
+            Self::TvShort => "tv_short",
+        }
+    }
+}
+
+/// A calendar date, or just a year, as accepted by the search date filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month_day: Option<(u8, u8)>,
+}
+
+impl Date {
+    pub fn year(year: u16) -> Self {
+        Self {
+            year,
+            month_day: None,
+        }
+    }
+
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, ImdbError> {
+        let days_in_month = Self::days_in_month(year, month)
+            .ok_or_else(|| ImdbError::InvalidQuery(format!("month {} does not exist", month)))?;
+        if day == 0 || day > days_in_month {
+            return Err(ImdbError::InvalidQuery(format!(
+                "{}-{:02} has no day {}",
+                year, month, day
+            )));
+        }
+
+        Ok(Self {
+            year,
+            month_day: Some((month, day)),
+        })
+    }
+
+    fn days_in_month(year: u16, month: u8) -> Option<u8> {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => Some(31),
+            4 | 6 | 9 | 11 => Some(30),
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => Some(29),
+            2 => Some(28),
+            _ => None,
+        }
+    }
+
+    /// Reads dates as IMDb writes them in lists, `17 Apr. 2011` or `25 May 1999`.
+    ///
+    /// Only the year is kept from partial dates like `Apr. 2011`.
+    pub(crate) fn parse_long(text: &str) -> Option<Self> {
+        const MONTHS: [&str; 12] = [
+            "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+        ];
+
+        let parts = text.split_whitespace().collect::<Vec<&str>>();
+        let year = parts.last()?.parse().ok()?;
+        match parts.as_slice() {
+            [day, month, _] => {
+                let month = month.get(..3)?.to_lowercase();
+                let month = MONTHS.iter().position(|m| *m == month)? as u8 + 1;
+                Self::new(year, month, day.parse().ok()?).ok()
+            }
+            _ => Some(Self::year(year)),
+        }
+    }
+
+    /// Reads dates as IMDb writes them in pages, `1977-05-25`, `1951-9-25` or `1977`.
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        match (parts.next(), parts.next()) {
+            (Some(month), Some(day)) => {
+                Self::new(year, month.parse().ok()?, day.parse().ok()?).ok()
+            }
+            _ => Some(Self::year(year)),
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.month_day {
+            Some((month, day)) => write!(f, "{:04}-{:02}-{:02}", self.year, month, day),
+            None => write!(f, "{:04}", self.year),
+        }
+    }
+}
+
+/// A rating certificate in a country, e.g. `US` `PG-13`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Certificate {
+    country: String,
+    rating: String,
+}
+
+impl Certificate {
+    pub fn new(country: &str, rating: &str) -> Result<Self, ImdbError> {
+        let rating = rating.trim();
+        if rating.is_empty() {
+            return Err(ImdbError::InvalidQuery(
+                "certificate rating is empty".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            country: country_code(country)?.to_uppercase(),
+            rating: rating.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortField {
+    Popularity,
+    Alphabetical,
+    UserRating,
+    NumVotes,
+    BoxOfficeUs,
+    Runtime,
+    Year,
+    ReleaseDate,
+}
+
+impl SortField {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Popularity => "moviemeter",
+            Self::Alphabetical => "alpha",
+            Self::UserRating => "user_rating",
+            Self::NumVotes => "num_votes",
+            Self::BoxOfficeUs => "boxoffice_gross_us",
+            Self::Runtime => "runtime",
+            Self::Year => "year",
+            Self::ReleaseDate => "release_date",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SortOrder {
+    field: SortField,
+    descending: bool,
+}
+
+impl SortOrder {
+    pub fn asc(field: SortField) -> Self {
+        Self {
+            field,
+            descending: false,
+        }
+    }
+
+    pub fn desc(field: SortField) -> Self {
+        Self {
+            field,
+            descending: true,
+        }
+    }
+
+    fn as_param(&self) -> String {
+        format!(
+            "{},{}",
+            self.field.as_param(),
+            if self.descending { "desc" } else { "asc" }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReviewSortField {
+    Helpfulness,
+    Date,
+    TotalVotes,
+    ReviewerProlificness,
+    Rating,
+}
+
+impl ReviewSortField {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Helpfulness => "helpfulnessScore",
+            Self::Date => "submissionDate",
+            Self::TotalVotes => "totalVotes",
+            Self::ReviewerProlificness => "reviewVolume",
+            Self::Rating => "userRating",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReviewSortOrder {
+    field: ReviewSortField,
+    descending: bool,
+}
+
+impl ReviewSortOrder {
+    pub fn asc(field: ReviewSortField) -> Self {
+        Self {
+            field,
+            descending: false,
+        }
+    }
+
+    pub fn desc(field: ReviewSortField) -> Self {
+        Self {
+            field,
+            descending: true,
+        }
+    }
+
+    /// `&sort=...&dir=...`, ready to append to the uri.
+    pub(crate) fn to_query_string(self) -> String {
+        format!(
+            "&sort={}&dir={}",
+            self.field.as_param(),
+            if self.descending { "desc" } else { "asc" }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gender {
+    Male,
+    Female,
+    NonBinary,
+    Other,
+}
+
+impl Gender {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Male => "male",
+            Self::Female => "female",
+            Self::NonBinary => "non_binary",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StarSign {
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+impl StarSign {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Aries => "aries",
+            Self::Taurus => "taurus",
+            Self::Gemini => "gemini",
+            Self::Cancer => "cancer",
+            Self::Leo => "leo",
+            Self::Virgo => "virgo",
+            Self::Libra => "libra",
+            Self::Scorpio => "scorpio",
+            Self::Sagittarius => "sagittarius",
+            Self::Capricorn => "capricorn",
+            Self::Aquarius => "aquarius",
+            Self::Pisces => "pisces",
+        }
+    }
+}
+
+/// Values with a smallest step, so that exclusive range bounds can be turned
+/// into the inclusive ones IMDb takes.
+pub(crate) trait Discrete: Sized {
+    fn next(self) -> Option<Self>;
+
+    fn previous(self) -> Option<Self>;
+}
+
+impl Discrete for u32 {
+    fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    fn previous(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+}
+
+/// User ratings go by tenths.
+impl Discrete for f32 {
+    fn next(self) -> Option<Self> {
+        Some(((self * 10.0).floor() + 1.0) / 10.0)
+    }
+
+    fn previous(self) -> Option<Self> {
+        Some(((self * 10.0).ceil() - 1.0) / 10.0)
+    }
+}
+
+/// The next day, or the next year for dates that are only a year.
+impl Discrete for Date {
+    fn next(self) -> Option<Self> {
+        let Some((month, day)) = self.month_day else {
+            return Some(Self::year(self.year.checked_add(1)?));
+        };
+        let month_day = if day < Self::days_in_month(self.year, month)? {
+            (self.year, month, day + 1)
+        } else if month < 12 {
+            (self.year, month + 1, 1)
+        } else {
+            (self.year.checked_add(1)?, 1, 1)
+        };
+        Self::new(month_day.0, month_day.1, month_day.2).ok()
+    }
+
+    fn previous(self) -> Option<Self> {
+        let Some((month, day)) = self.month_day else {
+            return Some(Self::year(self.year.checked_sub(1)?));
+        };
+        let month_day = if day > 1 {
+            (self.year, month, day - 1)
+        } else if month > 1 {
+            (
+                self.year,
+                month - 1,
+                Self::days_in_month(self.year, month - 1)?,
+            )
+        } else {
+            (self.year.checked_sub(1)?, 12, 31)
+        };
+        Self::new(month_day.0, month_day.1, month_day.2).ok()
+    }
+}
+
+/// An inclusive `min,max` filter, either side can be open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Span<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+impl<T: Copy + PartialOrd + fmt::Display + Discrete> Span<T> {
+    /// Exclusive bounds, like the end of `a..b`, become the step before or
+    /// after them.
+    pub(crate) fn new(name: &str, range: impl RangeBounds<T>) -> Result<Self, ImdbError> {
+        let empty = || ImdbError::InvalidQuery(format!("{} range is empty", name));
+        let span = Self {
+            min: match range.start_bound() {
+                Bound::Included(value) => Some(*value),
+                Bound::Excluded(value) => Some(value.next().ok_or_else(empty)?),
+                Bound::Unbounded => None,
+            },
+            max: match range.end_bound() {
+                Bound::Included(value) => Some(*value),
+                Bound::Excluded(value) => Some(value.previous().ok_or_else(empty)?),
+                Bound::Unbounded => None,
+            },
+        };
+
+        if let (Some(min), Some(max)) = (span.min, span.max) {
+            if min > max {
+                return Err(ImdbError::InvalidQuery(format!(
+                    "{} range {}..={} is empty",
+                    name, min, max
+                )));
+            }
+        }
+
+        Ok(span)
+    }
+
+    pub(crate) fn within(self, name: &str, lowest: T, highest: T) -> Result<Self, ImdbError> {
+        let out_of_bounds = |value: Option<T>| value.is_some_and(|v| v < lowest || v > highest);
+        if out_of_bounds(self.min) || out_of_bounds(self.max) {
+            return Err(ImdbError::InvalidQuery(format!(
+                "{} must be between {} and {}",
+                name, lowest, highest
+            )));
+        }
+
+        Ok(self)
+    }
+
+    fn as_param(&self) -> String {
+        let side = |value: Option<T>| value.map(|v| v.to_string()).unwrap_or_default();
+        format!("{},{}", side(self.min), side(self.max))
+    }
+}
+
+/// Validates an ISO 3166 alpha-2 country code.
+pub(crate) fn country_code(code: &str) -> Result<String, ImdbError> {
+    let code = code.trim();
+    if code.len() != 2 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ImdbError::InvalidQuery(format!(
+            "`{}` is not a two letter country code",
+            code
+        )));
+    }
+
+    Ok(code.to_lowercase())
+}
+
+/// Validates an ISO 639 language code.
+fn language_code(code: &str) -> Result<String, ImdbError> {
+    let code = code.trim();
+    if !(2..=3).contains(&code.len()) || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ImdbError::InvalidQuery(format!(
+            "`{}` is not a language code",
+            code
+        )));
+    }
+
+    Ok(code.to_lowercase())
+}
+
+/// Validates an IMDb id like `tt0076759`, `prefix` being `tt`, `nm`, ...
+pub(crate) fn imdb_id(prefix: &str, id: &str) -> Result<String, ImdbError> {
+    let id = id.trim();
+    let valid = id
+        .strip_prefix(prefix)
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
+        return Err(ImdbError::InvalidQuery(format!(
+            "`{}` is not an `{}` id",
+            id, prefix
+        )));
+    }
+
+    Ok(id.to_string())
+}
+
+/// Extra `/search/title/` parameters, see [`ByTitleBuilder`](super::by_title::ByTitleBuilder).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TitleFilters {
+    pub(crate) genres: Vec<Genre>,
+    pub(crate) title_types: Vec<TitleType>,
+    pub(crate) release_date: Option<Span<Date>>,
+    pub(crate) user_rating: Option<Span<f32>>,
+    pub(crate) num_votes: Option<Span<u32>>,
+    pub(crate) countries: Vec<String>,
+    pub(crate) languages: Vec<String>,
+    pub(crate) certificates: Vec<Certificate>,
+    pub(crate) runtime: Option<Span<u32>>,
+    pub(crate) sort: Option<SortOrder>,
+}
+
+impl TitleFilters {
+    pub(crate) fn add_countries<'a>(
+        &mut self,
+        codes: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), ImdbError> {
+        for code in codes {
+            self.countries.push(country_code(code)?);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn add_languages<'a>(
+        &mut self,
+        codes: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), ImdbError> {
+        for code in codes {
+            self.languages.push(language_code(code)?);
+        }
+        Ok(())
+    }
+
+    /// `&key=value` pairs, in a fixed order, ready to append to the uri.
+    pub(crate) fn to_query_string(&self) -> String {
+        let mut params = vec![];
+        let mut push_list = |name: &str, values: Vec<String>| {
+            if !values.is_empty() {
+                let values = values
+                    .iter()
+                    .map(|v| encode(v).to_string())
+                    .collect::<Vec<String>>();
+                params.push(format!("&{}={}", name, values.join(",")));
+            }
+        };
+
+        push_list(
+            "title_type",
+            self.title_types
+                .iter()
+                .map(|t| t.as_param().to_string())
+                .collect(),
+        );
+        push_list(
+            "genres",
+            self.genres
+                .iter()
+                .map(|g| g.as_param().to_string())
+                .collect(),
+        );
+        push_list("countries", self.countries.clone());
+        push_list("languages", self.languages.clone());
+        push_list(
+            "certificates",
+            self.certificates
+                .iter()
+                .map(|c| format!("{}:{}", c.country, c.rating))
+                .collect(),
+        );
+
+        let spans = [
+            ("release_date", self.release_date.map(|s| s.as_param())),
+            ("user_rating", self.user_rating.map(|s| s.as_param())),
+            ("num_votes", self.num_votes.map(|s| s.as_param())),
+            ("runtime", self.runtime.map(|s| s.as_param())),
+            ("sort", self.sort.map(|s| s.as_param())),
+        ];
+        for (name, value) in spans {
+            if let Some(value) = value {
+                params.push(format!("&{}={}", name, value));
+            }
+        }
+
+        params.concat()
+    }
+}
+
+/// Extra `/search/name/` parameters, see [`ByNameSearchBuilder`](super::by_name_search::ByNameSearchBuilder).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct NameFilters {
+    pub(crate) birth_date: Option<Span<Date>>,
+    pub(crate) death_date: Option<Span<Date>>,
+    pub(crate) birth_place: Option<String>,
+    pub(crate) genders: Vec<Gender>,
+    pub(crate) star_sign: Option<StarSign>,
+    pub(crate) known_for: Vec<String>,
+}
+
+impl NameFilters {
+    pub(crate) fn add_known_for<'a>(
+        &mut self,
+        ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), ImdbError> {
+        for id in ids {
+            self.known_for.push(imdb_id("tt", id)?);
+        }
+        Ok(())
+    }
+
+    /// `&key=value` pairs, in a fixed order, ready to append to the uri.
+    pub(crate) fn to_query_string(&self) -> String {
+        let genders = self
+            .genders
+            .iter()
+            .map(|g| g.as_param())
+            .collect::<Vec<&str>>()
+            .join(",");
+        let params = [
+            ("birth_date", self.birth_date.map(|s| s.as_param())),
+            ("death_date", self.death_date.map(|s| s.as_param())),
+            (
+                "birth_place",
+                self.birth_place.as_ref().map(|p| encode(p).to_string()),
+            ),
+            ("gender", Some(genders).filter(|g| !g.is_empty())),
+            (
+                "star_sign",
+                self.star_sign.map(|s| s.as_param().to_string()),
+            ),
+            (
+                "roles",
+                Some(self.known_for.join(",")).filter(|r| !r.is_empty()),
+            ),
+        ];
+
+        params
+            .iter()
+            .filter_map(|(name, value)| Some(format!("&{}={}", name, value.as_ref()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::prelude::ImdbSearchEngine;
+    use crate::search::{by::ByTitle, By};
+
+    use std::ops::Bound;
+
+    use super::{Certificate, Date, Genre, SortField, SortOrder, Span, TitleType};
+
+    #[test]
+    fn test_title_filters_in_uri() {
+        let by = ByTitle::builder()
+            .genres([Genre::Thriller])
+            .title_types([TitleType::Feature, TitleType::TvSeries])
+            .release_date(Date::year(2010)..=Date::new(2020, 12, 31).unwrap())
+            .user_rating(7.0..)
+            .num_votes(10_000..)
+            .countries(["US", "gb"])
+            .languages(["en"])
+            .certificates([Certificate::new("us", "PG-13").unwrap()])
+            .runtime(..=120)
+            .sort(SortOrder::desc(SortField::UserRating))
+            .count(50)
+            .build()
+            .unwrap();
+
+        let uri = by.get_uri(&ImdbSearchEngine::new(), "").unwrap();
+        assert_eq!(
+            uri.to_string(),
+            "https://www.imdb.com/search/title/?title=\
+             &title_type=feature,tv_series&genres=thriller&countries=us,gb&languages=en\
+             &certificates=US%3APG-13&release_date=2010,2020-12-31&user_rating=7,\
+             &num_votes=10000,&runtime=,120&sort=user_rating,desc&start=1&count=50"
+        );
+    }
+
+    #[test]
+    fn test_invalid_filters_are_rejected() {
+        assert!(Date::new(2021, 2, 29).is_err());
+        assert!(Date::new(2020, 2, 29).is_ok());
+        assert!(Certificate::new("usa", "R").is_err());
+        assert!(ByTitle::builder().user_rating(0.5..=7.0).build().is_err());
+        assert!(ByTitle::builder().user_rating(8.0..=7.0).build().is_err());
+        assert!(ByTitle::builder().user_rating(f32::NAN..).build().is_err());
+        assert!(ByTitle::builder().num_votes(10..10).build().is_err());
+        assert!(ByTitle::builder().num_votes(..0).build().is_err());
+        assert!(ByTitle::builder().languages(["english"]).build().is_err());
+    }
+
+    #[test]
+    fn test_exclusive_bounds_step_inward() {
+        let span = |range| Span::new("num_votes", range).unwrap().as_param();
+        assert_eq!(span(10..100), "10,99");
+        assert_eq!(
+            Span::new("user_rating", 7.0..8.0).unwrap().as_param(),
+            "7,7.9"
+        );
+        assert_eq!(
+            Span::new("user_rating", (Bound::Excluded(7.0), Bound::Unbounded))
+                .unwrap()
+                .as_param(),
+            "7.1,"
+        );
+        assert_eq!(
+            Span::new(
+                "release_date",
+                Date::year(2010)..Date::new(2021, 3, 1).unwrap()
+            )
+            .unwrap()
+            .as_param(),
+            "2010,2021-02-28"
+        );
+        assert_eq!(
+            Span::new("release_date", ..Date::new(2021, 1, 1).unwrap())
+                .unwrap()
+                .as_param(),
+            ",2020-12-31"
+        );
+        assert_eq!(
+            Span::new("release_date", ..Date::year(2021))
+                .unwrap()
+                .as_param(),
+            ",2020"
+        );
+    }
+}
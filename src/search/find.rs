@@ -0,0 +1,81 @@
+use hyper::Uri;
+use scraper::{ElementRef, Html};
+
+use crate::error::{ImdbError, ParseError};
+use crate::helpers::helper_tags::ATag;
+use crate::helpers::{
+    element_parser::ElementParserHelper, get_selector, html_parser::HtmlParserHelper,
+};
+use crate::ImdbSearchEngine;
+
+use super::parse_uri;
+
+/// Uri of the `/find` page for one category (`tt`, `nm`, ...).
+pub(crate) fn find_uri(
+    engine: &ImdbSearchEngine,
+    category: &str,
+    query: &str,
+) -> Result<Uri, ImdbError> {
+    parse_uri(format!("{}/find?s={}&q={}", engine.base_uri(), category, query).as_str())
+}
+
+pub(crate) fn find_rows(html: &Html) -> Vec<ElementRef<'_>> {
+    html.select_all("#main > div > div.findSection > table > tbody > tr")
+}
+
+/// The `div.findSection`s of the find page, one per category.
+pub(crate) fn find_sections(html: &Html) -> Vec<ElementRef<'_>> {
+    html.select_all("#main > div > div.findSection")
+}
+
+pub(crate) fn section_rows(section: ElementRef<'_>) -> Vec<ElementRef<'_>> {
+    let tr_selector = get_selector("table > tbody > tr");
+    section.select(&tr_selector).collect()
+}
+
+/// One `tr.findResult` of the find page.
+pub(crate) struct FindRow<'a> {
+    tr: ElementRef<'a>,
+    pub(crate) photo: Option<ElementRef<'a>>,
+    pub(crate) text: ElementRef<'a>,
+}
+
+impl<'a> FindRow<'a> {
+    pub(crate) fn parse(tr: ElementRef<'a>) -> Result<Self, ParseError> {
+        // Titles and names have two tds, the photo then the text, companies
+        // and keywords only have the text one.
+        let td_selector = get_selector("td");
+        let tds = tr.select(&td_selector).collect::<Vec<ElementRef>>();
+        let text = *tds
+            .last()
+            .ok_or_else(|| ParseError::new("td:nth-of-type(1)", &tr))?;
+        let photo = (tds.len() > 1).then(|| tds[0]);
+
+        Ok(Self { tr, photo, text })
+    }
+
+    pub(crate) fn image_src(&self) -> Result<String, ParseError> {
+        let photo = self
+            .photo
+            .ok_or_else(|| ParseError::new("td.primary_photo", &self.tr))?;
+        Ok(photo
+            .require_first("a > img")?
+            .require_attr("src")?
+            .to_string())
+    }
+
+    /// The first link of the text cell, the one naming the result.
+    pub(crate) fn main_link(&self) -> Result<ATag, ParseError> {
+        self.text.require_first("a")?.require_a_tag()
+    }
+
+    /// Text sitting directly in the text cell, like the `(1977)` after a title.
+    pub(crate) fn loose_text(&self) -> String {
+        self.text
+            .children()
+            .filter_map(|node| node.value().as_text())
+            .flat_map(|text| text.split_whitespace())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
@@ -1,17 +1,128 @@
+use std::str::FromStr;
+
 use hyper::Uri;
 use scraper::Html;
 
+use crate::error::{ImdbError, ParseError};
 use crate::ImdbSearchEngine;
 
 pub mod by;
+pub mod by_character;
+pub mod by_company;
+pub mod by_find_all;
+pub mod by_full_credits;
+pub mod by_keyword;
+pub mod by_name;
+pub mod by_name_id;
+pub mod by_name_search;
+pub mod by_parents_guide;
+pub mod by_ratings;
+pub mod by_release_info;
+pub mod by_reviews;
+pub mod by_season;
+pub mod by_technical_specs;
 pub mod by_title;
 pub mod by_title_find;
+pub mod by_title_id;
+pub mod filters;
+mod find;
+mod pagination;
 pub mod results;
 
 pub trait By {
     type ParseResult;
 
-    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Uri;
+    fn get_uri(&self, engine: &ImdbSearchEngine, query: &str) -> Result<Uri, ImdbError>;
+
+    /// Parsers of lists have a `lenient` option. A lenient parser skips the
+    /// items it cannot read and keeps their errors in the result's
+    /// `warnings()`, a strict one fails on the first of them.
+    fn parse_result(&self, html: Html) -> Result<Self::ParseResult, ParseError>;
+}
+
+/// A `By` whose results are split into pages.
+pub trait Paginated: By + Sized {
+    type Item;
+
+    /// The query for the page after `result`, `None` once there is nothing left.
+    fn next_page(&self, result: &Self::ParseResult) -> Option<Self>;
+
+    fn into_items(result: Self::ParseResult) -> Vec<Self::Item>;
+}
+
+pub(crate) fn parse_uri(uri: &str) -> Result<Uri, ImdbError> {
+    Uri::from_str(uri).map_err(|e| ImdbError::InvalidQuery(format!("`{}`: {}", uri, e)))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::prelude::{FixtureTransport, ImdbSearchEngine};
+
+    use super::by::{
+        ByFindAll, ByFullCredits, ByNameId, ByNameSearch, ByParentsGuide, ByRatings, ByReleaseInfo,
+        ByTechnicalSpecs, ByTitleId,
+    };
+    use super::By;
+
+    fn uri(by: impl By, query: &str) -> Option<String> {
+        Some(
+            by.get_uri(&ImdbSearchEngine::new(), query)
+                .ok()?
+                .to_string(),
+        )
+    }
+
+    #[test]
+    fn test_id_page_uris() {
+        let title_pages = [
+            (uri(ByTitleId, "tt0076759"), ""),
+            (uri(ByFullCredits::default(), "tt0076759"), "fullcredits"),
+            (uri(ByReleaseInfo::default(), "tt0076759"), "releaseinfo"),
+            (uri(ByParentsGuide, "tt0076759"), "parentalguide"),
+            (uri(ByTechnicalSpecs, "tt0076759"), "technical"),
+            (uri(ByRatings, "tt0076759"), "ratings"),
+        ];
+        for (uri, page) in title_pages {
+            assert_eq!(
+                uri.unwrap(),
+                format!("https://www.imdb.com/title/tt0076759/{}", page)
+            );
+        }
+        assert_eq!(
+            uri(ByNameId::default(), "nm0000434").unwrap(),
+            "https://www.imdb.com/name/nm0000434/"
+        );
+
+        // Ids of the wrong kind never reach the site.
+        assert_eq!(uri(ByTitleId, "nm0000434"), None);
+        assert_eq!(uri(ByTechnicalSpecs, "tt0076759/../x"), None);
+        assert_eq!(uri(ByNameId::default(), "tt0076759"), None);
+    }
+
+    // The fixture transport answers 404 for any other uri, so a query encoded
+    // twice (`mark%2520hamill`) fails the search.
+    #[tokio::test]
+    async fn test_engine_encodes_the_query_once() {
+        let engine = ImdbSearchEngine::builder()
+            .base_uri("http://fixtures")
+            .transport(
+                FixtureTransport::new()
+                    .with_page("http://fixtures/find?q=mark%20hamill", "<html></html>")
+                    .with_page(
+                        "http://fixtures/search/name/?name=mark%20hamill&start=1&count=50",
+                        "<html></html>",
+                    ),
+            )
+            .build()
+            .unwrap();
 
-    fn parse_result(&self, html: Html) -> Self::ParseResult;
+        assert!(engine
+            .search_by(ByFindAll::default(), "mark hamill")
+            .await
+            .is_ok());
+        assert!(engine
+            .search_by(ByNameSearch::default(), "mark hamill")
+            .await
+            .is_ok());
+    }
 }
@@ -0,0 +1,69 @@
+use std::ops::RangeInclusive;
+
+use scraper::Html;
+
+use crate::helpers::html_parser::HtmlParserHelper;
+
+/// Where a page of an advanced search (`/search/title/`, `/search/name/`)
+/// sits among all of its results.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PageInfo {
+    pub(crate) total_results: Option<u32>,
+    pub(crate) range: Option<RangeInclusive<u32>>,
+    pub(crate) next_start: Option<u16>,
+}
+
+impl PageInfo {
+    /// `start` is the one the page was requested with.
+    pub(crate) fn parse(html: &Html, start: u16) -> Self {
+        let (range, total_results) = Self::parse_results_header(html);
+        let next_start = Self::parse_next_start(html).or_else(|| {
+            let last = *range.as_ref()?.end();
+            let next = u16::try_from(last + 1).ok()?;
+            (last < total_results? && next > start).then_some(next)
+        });
+
+        Self {
+            total_results,
+            range,
+            next_start,
+        }
+    }
+
+    /// Reads the "1-50 of 3,385 titles." header, or "12 titles." when there
+    /// is a single page, into the range and the total.
+    fn parse_results_header(html: &Html) -> (Option<RangeInclusive<u32>>, Option<u32>) {
+        let header = match html.select_first("div.desc>span") {
+            Some(span) => span.inner_html(),
+            None => return (None, None),
+        };
+        let parse_number = |text: &str| text.trim().replace(',', "").parse::<u32>().ok();
+
+        match header.split_once(" of ") {
+            Some((range, total)) => {
+                let range = range
+                    .split_once('-')
+                    .and_then(|(first, last)| Some(parse_number(first)?..=parse_number(last)?));
+                (
+                    range,
+                    total.split_whitespace().next().and_then(parse_number),
+                )
+            }
+            None => {
+                let total = header.split_whitespace().next().and_then(parse_number);
+                (total.filter(|t| *t > 0).map(|t| 1..=t), total)
+            }
+        }
+    }
+
+    /// The `start` of the page behind the "Next »" link.
+    fn parse_next_start(html: &Html) -> Option<u16> {
+        let href = html
+            .select_first("div.desc>a.next-page")?
+            .value()
+            .attr("href")?;
+        let (_, start) = href.split_once("start=")?;
+
+        start.split('&').next()?.parse().ok()
+    }
+}
@@ -3,5 +3,67 @@ pub mod by_title {
 }
 
 pub mod by_title_find {
-    pub use crate::search::by_title_find::{ByTitleFound, ByTitleFoundItem};
+    pub use crate::search::by_title_find::{ByTitleFound, ByTitleFoundItem, TitleKind};
+}
+
+pub mod by_name {
+    pub use crate::search::by_name::{ByNameFound, ByNameFoundItem};
+}
+
+pub mod by_name_search {
+    pub use crate::search::by_name_search::{NameSearch, NameSearchItem};
+}
+
+pub mod by_company {
+    pub use crate::search::by_company::{ByCompanyFound, ByCompanyFoundItem};
+}
+
+pub mod by_keyword {
+    pub use crate::search::by_keyword::{ByKeywordFound, ByKeywordFoundItem};
+}
+
+pub mod by_character {
+    pub use crate::search::by_character::{ByCharacterFound, ByCharacterFoundItem};
+}
+
+pub mod by_find_all {
+    pub use crate::search::by_find_all::{FindAll, FindResult, FindSection};
+}
+
+pub mod by_title_id {
+    pub use crate::search::by_title_id::{CastMember, TitleDetails};
+}
+
+pub mod by_full_credits {
+    pub use crate::search::by_full_credits::{CreditEntry, Department, FullCredits};
+}
+
+pub mod by_name_id {
+    pub use crate::search::by_name_id::{FilmographyCategory, FilmographyEntry, PersonDetails};
+}
+
+pub mod by_season {
+    pub use crate::search::by_season::{Episode, Season, SeasonNumber};
+}
+
+pub mod by_parents_guide {
+    pub use crate::search::by_parents_guide::{
+        Advisory, AdvisoryCategory, AdvisoryItem, Certification, ParentsGuide, Severity,
+    };
+}
+
+pub mod by_ratings {
+    pub use crate::search::by_ratings::{AgeBracket, DemographicRating, Ratings};
+}
+
+pub mod by_release_info {
+    pub use crate::search::by_release_info::{Aka, Release, ReleaseAttribute, ReleaseInfo};
+}
+
+pub mod by_reviews {
+    pub use crate::search::by_reviews::{Review, Reviews};
+}
+
+pub mod by_technical_specs {
+    pub use crate::search::by_technical_specs::{Runtime, SpecValue, TechnicalSpecs};
 }